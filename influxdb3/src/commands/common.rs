@@ -1,4 +1,5 @@
-use clap::{Parser, ValueEnum};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, Command, FromArgMatches, Parser, ValueEnum};
 use influxdb3_types::http::FieldType;
 use observability_deps::tracing::warn;
 use secrecy::Secret;
@@ -7,8 +8,33 @@ use std::str::FromStr;
 use std::{env, error::Error};
 use url::Url;
 
-#[derive(Debug, Parser)]
+/// The options shared by the commands that operate on a database of a running server
+///
+/// The token can be given with `--token`, read from a file with `--token-file`, or taken from
+/// the `INFLUXDB3_AUTH_TOKEN` or `INFLUXDB3_AUTH_TOKEN_FILE` env vars. Passing it in a file or env
+/// var keeps it out of shell history and process listings. If more than one of these is set, the
+/// first of the following is used:
+///
+/// 1. `--token`
+/// 2. `--token-file`
+/// 3. `INFLUXDB3_AUTH_TOKEN`
+/// 4. `INFLUXDB3_AUTH_TOKEN_FILE`
+#[derive(Debug)]
 pub struct InfluxDb3Config {
+    /// The host URL of the running InfluxDB 3 Core server
+    pub host_url: Url,
+
+    /// The name of the database to operate on
+    pub database_name: String,
+
+    /// The token for authentication with the InfluxDB 3 Core server, from whichever of the token
+    /// options takes precedence
+    pub auth_token: Option<Secret<String>>,
+}
+
+/// The arguments parsed into an [`InfluxDb3Config`], before the token options are resolved
+#[derive(Debug, Parser)]
+struct InfluxDb3Args {
     /// The host URL of the running InfluxDB 3 Core server
     #[clap(
         short = 'H',
@@ -16,15 +42,77 @@ pub struct InfluxDb3Config {
         env = "INFLUXDB3_HOST_URL",
         default_value = "http://127.0.0.1:8181"
     )]
-    pub host_url: Url,
+    host_url: Url,
 
     /// The name of the database to operate on
     #[clap(short = 'd', long = "database", env = "INFLUXDB3_DATABASE_NAME")]
-    pub database_name: String,
+    database_name: String,
 
     /// The token for authentication with the InfluxDB 3 Core server
     #[clap(long = "token", env = "INFLUXDB3_AUTH_TOKEN", hide_env_values = true)]
-    pub auth_token: Option<Secret<String>>,
+    auth_token: Option<Secret<String>>,
+
+    /// A file containing the token for authentication with the InfluxDB 3 Core server
+    ///
+    /// Leading and trailing whitespace, e.g., a trailing newline, is ignored.
+    #[clap(
+        long = "token-file",
+        env = "INFLUXDB3_AUTH_TOKEN_FILE",
+        value_parser = read_token_file
+    )]
+    auth_token_file: Option<Secret<String>>,
+}
+
+impl InfluxDb3Args {
+    fn resolve(self, matches: &ArgMatches) -> InfluxDb3Config {
+        let from_command_line =
+            |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        let auth_token = match (self.auth_token, self.auth_token_file) {
+            (Some(_), Some(file))
+                if from_command_line("auth_token_file") && !from_command_line("auth_token") =>
+            {
+                Some(file)
+            }
+            (token, file) => token.or(file),
+        };
+        InfluxDb3Config {
+            host_url: self.host_url,
+            database_name: self.database_name,
+            auth_token,
+        }
+    }
+}
+
+impl FromArgMatches for InfluxDb3Config {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        InfluxDb3Args::from_arg_matches(matches).map(|args| args.resolve(matches))
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl Args for InfluxDb3Config {
+    fn augment_args(cmd: Command) -> Command {
+        InfluxDb3Args::augment_args(cmd)
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        InfluxDb3Args::augment_args_for_update(cmd)
+    }
+}
+
+/// Read a token from the file at `path`, ignoring leading and trailing whitespace
+fn read_token_file(path: &str) -> Result<Secret<String>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read token file '{path}': {e}"))?;
+    let token = contents.trim();
+    if token.is_empty() {
+        return Err(format!("token file '{path}' is empty"));
+    }
+    Ok(Secret::new(token.to_string()))
 }
 
 #[derive(Debug, ValueEnum, Clone, Copy)]
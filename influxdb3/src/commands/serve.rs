@@ -28,7 +28,8 @@ use influxdb3_processing_engine::virtualenv::find_python;
 use influxdb3_query_executor::{CreateQueryExecutorArgs, QueryExecutorImpl};
 use influxdb3_server::http::HttpApi;
 use influxdb3_server::{
-    CommonServerState, CreateServerArgs, Server, serve, serve_admin_token_recovery_endpoint,
+    AuthzCacheConfig, CommonServerState, CreateServerArgs, FlightMethod, GrpcCompressionConfig,
+    GrpcConfig, Server, serve, serve_admin_token_recovery_endpoint,
 };
 use influxdb3_shutdown::{ShutdownManager, ShutdownToken, wait_for_signal};
 use influxdb3_sys_events::SysEventStore;
@@ -522,6 +523,74 @@ pub struct Config {
     )]
     pub tls_minimum_version: TlsMinimumVersion,
 
+    /// Register the gRPC server reflection service, which lets tools like `grpcurl` discover
+    /// the gRPC services offered by the server. Disabled by default.
+    #[clap(
+        long = "grpc-reflection",
+        env = "INFLUXDB3_GRPC_REFLECTION",
+        default_value_t = false,
+        action
+    )]
+    pub grpc_reflection: bool,
+
+    /// Cache successful token authorizations for Flight (gRPC) queries for this long, e.g.,
+    /// "30s". A revoked token may continue to be accepted for Flight queries for up to this
+    /// duration. Authorizations are not cached by default.
+    #[clap(
+        long = "grpc-authz-cache-ttl",
+        env = "INFLUXDB3_GRPC_AUTHZ_CACHE_TTL",
+        value_parser = humantime::parse_duration
+    )]
+    pub grpc_authz_cache_ttl: Option<Duration>,
+
+    /// The maximum number of tokens to cache Flight (gRPC) authorizations for, used when
+    /// `--grpc-authz-cache-ttl` is set
+    #[clap(
+        long = "grpc-authz-cache-capacity",
+        env = "INFLUXDB3_GRPC_AUTHZ_CACHE_CAPACITY",
+        default_value = "1000"
+    )]
+    pub grpc_authz_cache_capacity: NonZeroUsize,
+
+    /// The compression encodings that Flight (gRPC) responses may be sent with, as a
+    /// comma-separated list of `gzip` and `zstd`, or `none` on its own to never compress
+    /// responses.
+    ///
+    /// Responses are only compressed for clients that advertise support for one of these
+    /// encodings, so clients that do not request compression are unaffected. Compressed requests
+    /// are always accepted.
+    #[clap(
+        long = "grpc-compression",
+        env = "INFLUXDB3_GRPC_COMPRESSION",
+        default_value = "gzip,zstd",
+        value_parser = GrpcCompression::parse_list
+    )]
+    pub grpc_compression: GrpcCompressionConfig,
+
+    /// The maximum number of Flight (gRPC) requests that can be in flight at once. Requests made
+    /// above this limit are rejected immediately with `RESOURCE_EXHAUSTED`, rather than queued.
+    /// Requests only count against the limit once their token is accepted, so requests with a
+    /// missing or invalid token are rejected as usual and never use up the limit. Requests for
+    /// the methods in `--grpc-authz-bypass` always count against it. Unlimited by default.
+    #[clap(
+        long = "grpc-max-concurrent-requests",
+        env = "INFLUXDB3_GRPC_MAX_CONCURRENT_REQUESTS"
+    )]
+    pub grpc_max_concurrent_requests: Option<NonZeroUsize>,
+
+    /// Flight (gRPC) methods to serve without checking the request's token, as a comma-separated
+    /// list of method names, e.g., `Handshake,ListActions`. None are bypassed by default.
+    ///
+    /// Bypassing the authorizer for a method that returns data, such as `DoGet`, exposes that
+    /// data to anyone who can reach the server. Requests that bypass the authorizer still count
+    /// against `--grpc-max-concurrent-requests`.
+    #[clap(
+        long = "grpc-authz-bypass",
+        env = "INFLUXDB3_GRPC_AUTHZ_BYPASS",
+        value_delimiter = ','
+    )]
+    pub grpc_authz_bypass: Vec<FlightMethod>,
+
     /// Provide a file path to write the address that the server is listening on to.
     ///
     /// This is mainly intended for testing purposes and is not considered stable.
@@ -672,6 +741,45 @@ impl From<&TlsMinimumVersion> for &'static [&'static SupportedProtocolVersion] {
     }
 }
 
+/// A compression encoding that Flight (gRPC) responses may be sent with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrpcCompression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl GrpcCompression {
+    /// Parse a comma-separated list of encodings, in which `none` cannot be combined with the
+    /// others
+    fn parse_list(s: &str) -> std::prelude::v1::Result<GrpcCompressionConfig, String> {
+        let encodings = s
+            .split(',')
+            .map(|encoding| encoding.trim().parse())
+            .collect::<std::prelude::v1::Result<Vec<Self>, _>>()?;
+        if encodings.len() > 1 && encodings.contains(&Self::None) {
+            return Err("none cannot be combined with other gRPC compression encodings".into());
+        }
+        Ok(GrpcCompressionConfig {
+            gzip: encodings.contains(&Self::Gzip),
+            zstd: encodings.contains(&Self::Zstd),
+        })
+    }
+}
+
+impl FromStr for GrpcCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err("Valid gRPC compression encodings are none, gzip, and zstd".into()),
+        }
+    }
+}
+
 /// Specified size of the Parquet cache in megabytes (MB)
 #[derive(Debug, Clone, Copy)]
 pub struct MemorySizeMb(usize);
@@ -1173,6 +1281,8 @@ pub async fn command(config: Config, user_params: HashMap<String, String>) -> Re
             cert_file: cert_file.clone(),
             key_file: key_file.clone(),
             tls_minimum_version: (&config.tls_minimum_version).into(),
+            grpc_config: GrpcConfig::default(),
+            flight_auditor: None,
         })
     });
 
@@ -1184,6 +1294,17 @@ pub async fn command(config: Config, user_params: HashMap<String, String>) -> Re
         cert_file,
         key_file,
         tls_minimum_version: (&config.tls_minimum_version).into(),
+        grpc_config: GrpcConfig {
+            reflection: config.grpc_reflection,
+            authz_cache: config.grpc_authz_cache_ttl.map(|ttl| AuthzCacheConfig {
+                ttl,
+                capacity: config.grpc_authz_cache_capacity,
+            }),
+            compression: config.grpc_compression,
+            max_concurrent_requests: config.grpc_max_concurrent_requests,
+            authz_bypass: config.grpc_authz_bypass.clone(),
+        },
+        flight_auditor: None,
     });
 
     // There are two different select! macros - tokio::select and futures::select
@@ -1,22 +1,116 @@
-use std::path::PathBuf;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use clap::Parser;
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use arrow::util::{display::array_value_to_string, pretty::pretty_format_batches};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::{Parser, ValueEnum};
+use futures::{StreamExt, TryStreamExt};
 use influxdb3_client::Client;
+use observability_deps::tracing::{debug, warn};
+use parquet::arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder};
+use reqwest::header::{HeaderName, HeaderValue};
 use secrecy::ExposeSecret;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
 
-use super::super::common::{Format, InfluxDb3Config};
+use super::super::common::{Format, InfluxDb3Config, parse_key_val};
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
     #[error("client error: {0}")]
     InfluxDB3Client(#[from] influxdb3_client::Error),
 
-    #[error("deserializing show tables: {0}")]
-    DeserializingShowTables(#[source] serde_json::Error),
+    #[error(
+        "the {what} returned by the server are not in the expected form, check that the server \
+        is running a compatible version of InfluxDB 3: {source}"
+    )]
+    UnexpectedSchema {
+        what: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(
+        "no system tables were found in database '{0}', check that the server is running \
+        InfluxDB 3 and that the token is allowed to query system tables"
+    )]
+    NoSystemTables(String),
+
+    #[error("deserializing row count: {0}")]
+    DeserializingRowCount(#[source] serde_json::Error),
+
+    #[error("deserializing summary: {0}")]
+    DeserializingSummary(#[source] serde_json::Error),
+
+    #[error("parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[error("arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+
+    #[error("serializing output: {0}")]
+    SerializingOutput(#[source] serde_json::Error),
+
+    #[error("writing csv output: {0}")]
+    WritingCsv(#[from] csv::Error),
 
     #[error("system table '{0}' not found: {1}")]
-    SystemTableNotFound(String, SystemTableNotFound),
+    UnknownTable(String, SystemTableNotFound),
+
+    #[error("system table '{table_name}' has no column '{column}': {not_found}")]
+    UnknownColumn {
+        table_name: String,
+        column: String,
+        not_found: ColumnNotFound,
+    },
+
+    #[error("invalid UTF8 received from server: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(
+        "--after requires the entries to be ordered by a single column, use --order-by to choose \
+        the column to page by"
+    )]
+    AfterWithoutSingleOrdering,
+
+    #[error(
+        "--after cannot be used with system table '{0}', as it has no column that identifies each \
+        entry, so entries that share the ordering value at the end of a page would be skipped"
+    )]
+    AfterWithoutUniqueColumn(String),
+
+    #[error(
+        "must specify an output file path with `--output` parameter when formatting the output \
+        as `parquet`"
+    )]
+    NoOutputFileForParquet,
+
+    #[error(
+        "the `parquet` format can only be used with `--output`, which this command does not support"
+    )]
+    ParquetNotSupported,
+
+    #[error("system table '{0}' does not have a known time column, so {1} cannot be used with it")]
+    NoTimeColumn(String, &'static str),
+
+    #[error("--agg {0} requires the columns to aggregate to be given with --select")]
+    AggregateWithoutSelect(Aggregate),
+
+    #[error("server at {url} failed the connectivity check: {source}")]
+    ServerCheck {
+        url: String,
+        #[source]
+        source: influxdb3_client::Error,
+    },
 }
 
 pub(super) type Result<T> = std::result::Result<T, Error>;
@@ -38,20 +132,81 @@ pub(super) enum SubCommand {
     TableList(TableListConfig),
     /// Retrieve entries from a specific system table.
     Table(TableConfig),
-    /// Summarize various types of system table data.
+    /// Summarize various types of system table data. Several databases can be summarized at once
+    /// by passing a comma-separated list to `--database`.
     Summary(SummaryConfig),
+    /// Describe the columns and data types of a specific system table as JSON.
+    Describe(DescribeConfig),
+}
+
+/// Options for the client used to connect to the server, common to all subcommands
+#[derive(Debug, clap::Args)]
+pub(super) struct ClientConfig {
+    /// An optional arg to use a custom ca for useful for testing with self signed certs
+    #[clap(long = "tls-ca", visible_alias = "ca-cert", env = "INFLUXDB3_TLS_CA")]
+    ca_cert: Option<PathBuf>,
+
+    /// The path to a PEM encoded client certificate, presented to servers that require mutual
+    /// TLS. Must be given along with `--key`.
+    #[clap(
+        long = "cert",
+        env = "INFLUXDB3_TLS_CLIENT_CERT",
+        requires = "client_key"
+    )]
+    client_cert: Option<PathBuf>,
+
+    /// The path to the PEM encoded private key for the `--cert` client certificate
+    #[clap(
+        long = "key",
+        env = "INFLUXDB3_TLS_CLIENT_KEY",
+        requires = "client_cert"
+    )]
+    client_key: Option<PathBuf>,
+
+    /// The number of times to retry a query that failed due to a connection error or a server
+    /// error response, using exponential backoff between attempts. Client errors, e.g., an
+    /// invalid query, are never retried.
+    #[clap(long = "retries", default_value_t = 0)]
+    retries: u32,
+
+    /// The maximum time to wait for each request to the server to complete, e.g., "30s" or
+    /// "2m". Use "0s" to wait indefinitely. Results written to a file with `--output` or
+    /// `--output-dir` are streamed, and may take longer than this, as long as the server does not
+    /// stall for this long while sending them.
+    #[clap(long = "timeout", default_value = "30s", value_parser = humantime::parse_duration)]
+    timeout: Duration,
+
+    /// An additional HTTP header to send with each request, in the form `NAME:VALUE`, e.g.,
+    /// `--header x-tenant-id:tenant-a`. Can be given multiple times.
+    #[clap(long = "header", value_parser = parse_key_val::<HeaderName, HeaderValue>)]
+    headers: Vec<(HeaderName, HeaderValue)>,
+
+    /// Ping the server before running the command, to fail fast with a clear error if it cannot
+    /// be reached or the auth token is rejected
+    #[clap(long = "check", default_value_t = false)]
+    check: bool,
+}
+
+impl SubCommand {
+    fn client_config(&self) -> &ClientConfig {
+        match self {
+            Self::TableList(TableListConfig { client_config, .. })
+            | Self::Table(TableConfig { client_config, .. })
+            | Self::Summary(SummaryConfig { client_config, .. })
+            | Self::Describe(DescribeConfig { client_config, .. }) => client_config,
+        }
+    }
 }
 
 pub(super) async fn command(config: SystemConfig) -> Result<()> {
+    let client_config = config.subcommand.client_config();
     let mut client = Client::new(
         config.core_config.host_url.clone(),
-        match config.subcommand {
-            SubCommand::TableList(TableListConfig { ref ca_cert, .. }) => ca_cert,
-            SubCommand::Table(TableConfig { ref ca_cert, .. }) => ca_cert,
-            SubCommand::Summary(SummaryConfig { ref ca_cert, .. }) => ca_cert,
-        }
-        .clone(),
+        client_config.ca_cert.clone(),
     )?;
+    if let (Some(cert), Some(key)) = (&client_config.client_cert, &client_config.client_key) {
+        client = client.with_client_identity(&std::fs::read(cert)?, &std::fs::read(key)?)?;
+    }
     if let Some(token) = config
         .core_config
         .auth_token
@@ -60,21 +215,105 @@ pub(super) async fn command(config: SystemConfig) -> Result<()> {
     {
         client = client.with_auth_token(token);
     }
+    if !client_config.timeout.is_zero() {
+        client = client
+            .with_timeout(client_config.timeout)
+            .with_connect_timeout(client_config.timeout)?
+            .with_read_timeout(client_config.timeout)?;
+    }
+    for (name, value) in &client_config.headers {
+        client = client.with_header(name.clone(), value.clone());
+    }
 
     let runner = SystemCommandRunner {
         client,
         db: config.core_config.database_name.clone(),
+        retries: client_config.retries,
     };
+    if client_config.check {
+        runner.check(config.core_config.host_url.as_str()).await?;
+    }
     match config.subcommand {
         SubCommand::Table(cfg) => runner.get(cfg).await,
         SubCommand::TableList(cfg) => runner.list(cfg).await,
         SubCommand::Summary(cfg) => runner.summary(cfg).await,
+        SubCommand::Describe(cfg) => runner.describe(cfg).await,
     }
 }
 
 struct SystemCommandRunner {
     client: Client,
     db: String,
+    retries: u32,
+}
+
+impl SystemCommandRunner {
+    /// Create a runner that queries the given database using the same client
+    fn with_db(&self, db: &str) -> Self {
+        Self {
+            client: self.client.clone(),
+            db: db.to_string(),
+            retries: self.retries,
+        }
+    }
+
+    /// Ping the server to verify that it is reachable and accepts the configured auth token
+    async fn check(&self, url: &str) -> Result<()> {
+        let ping = self
+            .with_retries(|| self.client.ping())
+            .await
+            .map_err(|source| Error::ServerCheck {
+                url: url.to_string(),
+                source,
+            })?;
+        debug!(
+            version = ping.version(),
+            revision = ping.revision(),
+            "server check passed"
+        );
+        Ok(())
+    }
+
+    /// The delay before the first retry, which doubles on each subsequent attempt
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    /// The maximum delay between retries
+    const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+    /// Run the request produced by `f`, retrying up to `self.retries` times if it fails with a
+    /// transient error
+    async fn with_retries<T, F, Fut>(&self, f: F) -> std::result::Result<T, influxdb3_client::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, influxdb3_client::Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    let backoff = Self::INITIAL_BACKOFF
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                        .min(Self::MAX_BACKOFF);
+                    debug!(error = %e, ?backoff, attempt, "retrying failed request");
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Whether a client error is worth retrying, i.e., the server could not be reached or it
+/// responded with a server error
+fn is_transient(e: &influxdb3_client::Error) -> bool {
+    match e {
+        influxdb3_client::Error::RequestSend { source, .. } => {
+            source.is_connect() || source.is_timeout()
+        }
+        influxdb3_client::Error::Timeout { .. } => true,
+        influxdb3_client::Error::ApiError { code, .. } => code.is_server_error(),
+        _ => false,
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -82,22 +321,90 @@ struct ShowTablesRow {
     table_name: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct ShowColumnsRow {
+    column_name: String,
+    data_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemColumnRow {
+    table_name: String,
+    column_name: String,
+    data_type: String,
+}
+
+/// A system table and its columns, as output by `table-list --format json` or `--format jsonl`
+#[derive(Debug, Serialize)]
+struct TableListEntry {
+    table: String,
+    columns: Vec<TableListColumn>,
+}
+
+#[derive(Debug, Serialize)]
+struct TableListColumn {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+}
+
 #[derive(Debug, Parser)]
 pub(super) struct TableListConfig {
     /// The format in which to output the query
     #[clap(value_enum, long = "format", default_value = "pretty")]
     output_format: Format,
 
-    /// An optional arg to use a custom ca for useful for testing with self signed certs
-    #[clap(long = "tls-ca", env = "INFLUXDB3_TLS_CA")]
-    ca_cert: Option<PathBuf>,
+    /// Show the data type alongside each column name. The `json`, `jsonl`, and `csv` formats
+    /// always include the data types.
+    #[clap(long = "types", default_value_t = false)]
+    types: bool,
+
+    /// Only list the names of the system tables, without their columns.
+    #[clap(long = "names-only", default_value_t = false, conflicts_with = "types")]
+    names_only: bool,
+
+    /// Only list the columns whose names contain the given text, ignoring case, e.g.,
+    /// `--grep duration`. Tables without a matching column are listed with no columns.
+    #[clap(long = "grep", conflicts_with = "names_only")]
+    grep: Option<String>,
+
+    #[clap(flatten)]
+    client_config: ClientConfig,
 }
 
 const SYS_TABLES_QUERY: &str = "WITH cols (table_name, column_name) AS (SELECT table_name, column_name FROM information_schema.columns WHERE table_schema = 'system' ORDER BY (table_name, column_name)) SELECT table_name, array_agg(column_name) AS column_names FROM cols GROUP BY table_name ORDER BY table_name";
 
+const SYS_TABLE_NAMES_QUERY: &str = "SELECT table_name FROM information_schema.tables WHERE table_schema = 'system' ORDER BY table_name";
+
+const SYS_COLUMNS_QUERY: &str = "SELECT table_name, column_name, data_type FROM information_schema.columns WHERE table_schema = 'system' ORDER BY table_name, column_name";
+
+const SYS_TABLE_COLUMNS_QUERY: &str = "SELECT column_name, data_type FROM information_schema.columns WHERE table_schema = 'system' AND table_name = $table_name ORDER BY ordinal_position";
+
+const SYS_TABLES_WITH_TYPES_QUERY: &str = "WITH cols (table_name, column_name, data_type) AS (SELECT table_name, column_name, data_type FROM information_schema.columns WHERE table_schema = 'system' ORDER BY (table_name, column_name)) SELECT table_name, array_agg(column_name || ': ' || data_type) AS columns FROM cols GROUP BY table_name ORDER BY table_name";
+
 #[derive(Debug)]
 pub(super) struct SystemTableNotFound {
     system_tables: Vec<ShowTablesRow>,
+    suggestion: Option<String>,
+}
+
+impl SystemTableNotFound {
+    /// The maximum edit distance for a system table name to be suggested in place of an unknown
+    /// table name
+    const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+    fn new(system_tables: Vec<ShowTablesRow>, table_name: &str) -> Self {
+        let suggestion = system_tables
+            .iter()
+            .map(|t| (edit_distance(&t.table_name, table_name), &t.table_name))
+            .filter(|(d, _)| *d <= Self::MAX_SUGGESTION_DISTANCE)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, name)| name.clone());
+        Self {
+            system_tables,
+            suggestion,
+        }
+    }
 }
 
 impl std::fmt::Display for SystemTableNotFound {
@@ -107,24 +414,235 @@ impl std::fmt::Display for SystemTableNotFound {
                 acc.push(v.table_name.clone());
                 acc
             });
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "did you mean '{suggestion}'? ")?;
+        }
         writeln!(f, "please use a valid system table name: {system_tables:?}")?;
         Ok(())
     }
 }
 
+#[derive(Debug)]
+pub(super) struct ColumnNotFound {
+    columns: Vec<String>,
+    suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ColumnNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "did you mean '{suggestion}'? ")?;
+        }
+        write!(f, "valid columns are: {:?}", self.columns)
+    }
+}
+
+/// Check that each column referenced by `exprs`, as passed to `--select` or `--order-by`, exists
+/// in the table
+///
+/// Only plain column names, optionally followed by `ASC` or `DESC`, are checked. Anything else,
+/// e.g., `*`, a quoted identifier, or an aggregate like `count(*)`, is left for the server to
+/// validate.
+fn validate_columns<'a>(
+    table_name: &str,
+    columns: &[ShowColumnsRow],
+    exprs: impl IntoIterator<Item = &'a str>,
+) -> Result<()> {
+    for expr in exprs {
+        let (column, _) = split_ordering(expr);
+        let is_plain = !column.is_empty()
+            && column
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_plain
+            || columns
+                .iter()
+                .any(|c| c.column_name.eq_ignore_ascii_case(column))
+        {
+            continue;
+        }
+        let suggestion = columns
+            .iter()
+            .map(|c| (edit_distance(&c.column_name, column), &c.column_name))
+            .filter(|(d, _)| *d <= SystemTableNotFound::MAX_SUGGESTION_DISTANCE)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, name)| name.clone());
+        return Err(Error::UnknownColumn {
+            table_name: table_name.to_string(),
+            column: column.to_string(),
+            not_found: ColumnNotFound {
+                columns: columns.iter().map(|c| c.column_name.clone()).collect(),
+                suggestion,
+            },
+        });
+    }
+    Ok(())
+}
+
+/// Compute the Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Keep only the columns of each table whose names contain `text`, ignoring case
+fn grep_columns(mut tables: Vec<TableListEntry>, text: &str) -> Vec<TableListEntry> {
+    let text = text.to_lowercase();
+    for table in &mut tables {
+        table
+            .columns
+            .retain(|c| c.name.to_lowercase().contains(&text));
+    }
+    tables
+}
+
+/// Print tables and their columns in the given format, as `list` would, for tables whose columns
+/// have been filtered on the client
+fn print_table_columns(tables: &[TableListEntry], format: Format, types: bool) -> Result<()> {
+    match format {
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(tables).map_err(Error::SerializingOutput)?
+        ),
+        Format::JsonLines => {
+            for table in tables {
+                println!(
+                    "{}",
+                    serde_json::to_string(table).map_err(Error::SerializingOutput)?
+                );
+            }
+        }
+        Format::Csv => {
+            // data types such as `Timestamp(Nanosecond, None)` contain commas, so they are quoted
+            let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+            writer.write_record(["table", "column", "type"])?;
+            for table in tables {
+                if table.columns.is_empty() {
+                    writer.write_record([table.table.as_str(), "", ""])?;
+                }
+                for column in &table.columns {
+                    writer.write_record([&table.table, &column.name, &column.data_type])?;
+                }
+            }
+            writer.flush()?;
+        }
+        Format::Pretty => {
+            for table in tables {
+                let columns = table
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        if types {
+                            format!("{}: {}", c.name, c.data_type)
+                        } else {
+                            c.name.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if columns.is_empty() {
+                    println!("{}", table.table);
+                } else {
+                    println!("{}: {}", table.table, columns.join(", "));
+                }
+            }
+        }
+        Format::Parquet => return Err(Error::ParquetNotSupported),
+    }
+    Ok(())
+}
+
 impl SystemCommandRunner {
     async fn list(&self, config: TableListConfig) -> Result<()> {
+        if config.output_format.is_parquet() {
+            return Err(Error::ParquetNotSupported);
+        }
+        if config.names_only {
+            let bs = self
+                .with_retries(|| {
+                    self.client
+                        .api_v3_query_sql(self.db.as_str(), SYS_TABLE_NAMES_QUERY)
+                        .format(config.output_format.into())
+                        .send()
+                })
+                .await?;
+            println!("{}", std::str::from_utf8(&bs)?);
+            return Ok(());
+        }
+        if let Some(grep) = &config.grep {
+            let tables = grep_columns(self.get_system_table_columns().await?, grep);
+            return print_table_columns(&tables, config.output_format, config.types);
+        }
+        match config.output_format {
+            // the json and csv formats are built on the client so that each table is output with
+            // the same shape, and encoded the same way, whether or not its columns are filtered
+            // with `--grep`
+            Format::Json | Format::JsonLines | Format::Csv => {
+                let tables = self.get_system_table_columns().await?;
+                return print_table_columns(&tables, config.output_format, config.types);
+            }
+            Format::Pretty | Format::Parquet => {}
+        }
+
+        let query = if config.types {
+            SYS_TABLES_WITH_TYPES_QUERY
+        } else {
+            SYS_TABLES_QUERY
+        };
         let bs = self
-            .client
-            .api_v3_query_sql(self.db.as_str(), SYS_TABLES_QUERY)
-            .format(config.output_format.into())
-            .send()
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), query)
+                    .format(config.output_format.into())
+                    .send()
+            })
             .await?;
 
-        println!("{}", String::from_utf8(bs.as_ref().to_vec()).unwrap());
+        println!("{}", std::str::from_utf8(&bs)?);
 
         Ok(())
     }
+
+    /// Get every system table along with its columns, in the order they are listed by `list`
+    async fn get_system_table_columns(&self) -> Result<Vec<TableListEntry>> {
+        let bs = self
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), SYS_COLUMNS_QUERY)
+                    .format(Format::Json.into())
+                    .send()
+            })
+            .await?;
+        let rows = deserialize_rows::<SystemColumnRow>(&bs, "system table columns")?;
+        if rows.is_empty() {
+            return Err(Error::NoSystemTables(self.db.clone()));
+        }
+
+        // rows are ordered by table name, so each table's columns are contiguous
+        let mut tables: Vec<TableListEntry> = Vec::new();
+        for row in rows {
+            let column = TableListColumn {
+                name: row.column_name,
+                data_type: row.data_type,
+            };
+            match tables.last_mut() {
+                Some(table) if table.table == row.table_name => table.columns.push(column),
+                _ => tables.push(TableListEntry {
+                    table: row.table_name,
+                    columns: vec![column],
+                }),
+            }
+        }
+        Ok(tables)
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -137,48 +655,198 @@ pub(super) struct TableConfig {
     #[clap(long = "limit", short = 'l', default_value_t = 100)]
     limit: u16,
 
-    /// Order by the specified fields.
+    /// Order by the specified fields. If not provided, well-known system tables are ordered by a
+    /// sensible default, e.g., `queries` by `end2end_duration`.
     #[clap(long = "order-by", short = 'o', num_args = 1, value_delimiter = ',')]
     order_by: Vec<String>,
 
+    /// Do not apply the default ordering for the system table when `--order-by` is not provided.
+    #[clap(
+        long = "no-default-order",
+        default_value_t = false,
+        conflicts_with = "order_by"
+    )]
+    no_default_order: bool,
+
     /// Select specified fields from table.
     #[clap(long = "select", short = 's', num_args = 1, value_delimiter = ',')]
     select: Vec<String>,
 
+    /// Filter table entries using a SQL predicate, e.g., `--where "success = false"`. Can be
+    /// passed multiple times, in which case the predicates are combined with `AND`.
+    #[clap(long = "where", short = 'w', num_args = 1)]
+    where_filters: Vec<String>,
+
+    /// Only show table entries at or after this time, given either as an RFC3339 timestamp, e.g.,
+    /// `2025-01-01T00:00:00Z`, or as a duration relative to now, e.g., `1h` or `30m`.
+    #[clap(long = "since")]
+    since: Option<TimeBound>,
+
+    /// Only show table entries before this time, given either as an RFC3339 timestamp, e.g.,
+    /// `2025-01-01T00:00:00Z`, or as a duration relative to now, e.g., `1h` or `30m`.
+    #[clap(long = "until")]
+    until: Option<TimeBound>,
+
+    /// Only show table entries that come after this value of the ordering column, for paging
+    /// through a large table without re-reading earlier entries. The entries must be ordered by
+    /// a single column, either with `--order-by` or by default. When the page is full, the value
+    /// to pass to `--after` for the next page is printed to stderr.
+    ///
+    /// Only tables with a column that identifies each entry can be paged through. When the
+    /// ordering column is not that column, entries that share the ordering value are ordered by
+    /// it, and `--after-key` gives the value it had in the last entry of the previous page.
+    #[clap(long = "after")]
+    after: Option<String>,
+
+    /// The value of the column that identifies each entry in the last entry of the previous page,
+    /// for the entries that share the `--after` value of the ordering column. This is printed to
+    /// stderr along with `--after` when it is needed.
+    #[clap(long = "after-key", requires = "after")]
+    after_key: Option<String>,
+
+    /// Aggregate the table entries into buckets of this duration, e.g., `1m`, by the table's time
+    /// column, rather than showing each entry. The buckets are output in time order, and `--agg`
+    /// chooses how the entries in each bucket are aggregated. The duration must not be zero.
+    #[clap(
+        long = "group-by-interval",
+        value_parser = parse_interval,
+        conflicts_with_all = ["order_by", "after", "count_only"]
+    )]
+    group_by_interval: Option<Duration>,
+
+    /// The aggregate function applied to each `--select` column when using `--group-by-interval`.
+    /// By default, the entries in each bucket are counted.
+    #[clap(value_enum, long = "agg", requires = "group_by_interval")]
+    agg: Option<Aggregate>,
+
     /// The format in which to output the query
     #[clap(value_enum, long = "format", default_value = "pretty")]
     output_format: Format,
 
-    /// An optional arg to use a custom ca for useful for testing with self signed certs
-    #[clap(long = "tls-ca", env = "INFLUXDB3_TLS_CA")]
-    ca_cert: Option<PathBuf>,
+    /// Write the table entries to the file at `output` instead of stdout
+    #[clap(long = "output", short = 'O')]
+    output_file_path: Option<PathBuf>,
+
+    /// Only output the number of matching table entries, rather than the entries themselves.
+    /// `--select`, `--order-by`, and `--limit` are ignored.
+    #[clap(long = "count-only", default_value_t = false)]
+    count_only: bool,
+
+    /// Output the plan for the query that would be run, using `EXPLAIN`, rather than the table
+    /// entries.
+    #[clap(
+        long = "explain",
+        default_value_t = false,
+        conflicts_with_all = ["explain_analyze", "count_only"]
+    )]
+    explain: bool,
+
+    /// Run the query and output its plan with execution metrics, using `EXPLAIN ANALYZE`, rather
+    /// than the table entries.
+    #[clap(
+        long = "explain-analyze",
+        default_value_t = false,
+        conflicts_with = "count_only"
+    )]
+    explain_analyze: bool,
+
+    /// Print how long the queries took to stderr, so that it is kept apart from the output.
+    #[clap(long = "timing", default_value_t = false)]
+    timing: bool,
+
+    #[clap(flatten)]
+    client_config: ClientConfig,
 }
 
 impl SystemCommandRunner {
     async fn get_system_tables(&self) -> Result<Vec<ShowTablesRow>> {
         let bs = self
-            .client
-            .api_v3_query_sql(self.db.as_str(), SYS_TABLES_QUERY)
-            .format(Format::Json.into())
-            .send()
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), SYS_TABLES_QUERY)
+                    .format(Format::Json.into())
+                    .send()
+            })
             .await?;
 
-        serde_json::from_slice::<Vec<ShowTablesRow>>(bs.as_ref())
-            .map_err(Error::DeserializingShowTables)
+        let tables = deserialize_rows::<ShowTablesRow>(&bs, "system tables")?;
+        if tables.is_empty() {
+            return Err(Error::NoSystemTables(self.db.clone()));
+        }
+        Ok(tables)
     }
 
     async fn get(&self, config: TableConfig) -> Result<()> {
-        let Self { client, db } = self;
+        let Self { client, db, .. } = self;
         let TableConfig {
             system_table: system_table_name,
             limit,
             select,
             order_by,
+            no_default_order,
+            where_filters,
+            since,
+            until,
+            after,
+            after_key,
+            group_by_interval,
+            agg,
             output_format,
+            output_file_path,
+            count_only,
+            explain,
+            explain_analyze,
+            timing,
             ..
         } = config;
 
-        let select_expr = if !select.is_empty() {
+        if output_file_path.is_none() && output_format.is_parquet() && !count_only {
+            return Err(Error::NoOutputFileForParquet);
+        }
+
+        // Validate the table name and columns before building the query, so that unknown or
+        // malformed names produce a helpful error instead of breaking the SQL sent to the server
+        let columns = self.get_table_columns(&system_table_name).await?;
+        if columns.is_empty() {
+            let system_tables = self.get_system_tables().await?;
+            let not_found = SystemTableNotFound::new(system_tables, &system_table_name);
+            return Err(Error::UnknownTable(system_table_name, not_found));
+        }
+        if !count_only {
+            validate_columns(
+                &system_table_name,
+                &columns,
+                select.iter().chain(&order_by).map(String::as_str),
+            )?;
+        }
+
+        let group_by = match group_by_interval {
+            Some(interval) => {
+                let Some(time_column) = time_column(&system_table_name) else {
+                    return Err(Error::NoTimeColumn(
+                        system_table_name,
+                        "--group-by-interval",
+                    ));
+                };
+                Some((interval, time_column))
+            }
+            None => None,
+        };
+
+        let select_expr = if let Some((interval, time_column)) = group_by {
+            let aggregates = match (agg.unwrap_or(Aggregate::Count), select.as_slice()) {
+                (Aggregate::Count, []) => vec!["COUNT(*) AS count".to_string()],
+                (agg, []) => return Err(Error::AggregateWithoutSelect(agg)),
+                (agg, select) => select
+                    .iter()
+                    .map(|column| format!("{agg}({column}) AS {agg}_{column}"))
+                    .collect(),
+            };
+            std::iter::once(time_bucket(interval, time_column))
+                .chain(aggregates)
+                .collect::<Vec<_>>()
+                .join(",")
+        } else if !select.is_empty() {
             select.join(",")
         } else {
             "*".to_string()
@@ -188,13 +856,117 @@ impl SystemCommandRunner {
             "SELECT {select_expr} FROM system.\"{system_table_name}\""
         )];
 
-        if let Some(default_filter) = default_filter(&system_table_name) {
-            clauses.push(format!("WHERE {default_filter}"));
+        let mut filters = default_filter(&system_table_name)
+            .into_iter()
+            .chain(where_filters)
+            .collect::<Vec<_>>();
+
+        if since.is_some() || until.is_some() {
+            let Some(time_column) = time_column(&system_table_name) else {
+                return Err(Error::NoTimeColumn(
+                    system_table_name,
+                    "--since and --until",
+                ));
+            };
+            let now = Utc::now();
+            if let Some(since) = since {
+                filters.push(format!("{time_column} >= {}", since.to_sql(now)));
+            }
+            if let Some(until) = until {
+                filters.push(format!("{time_column} < {}", until.to_sql(now)));
+            }
         }
 
-        if !order_by.is_empty() {
+        let default_ordering = default_ordering(&system_table_name);
+        let keyset = match &after {
+            Some(after) => {
+                let ordering = match (order_by.as_slice(), &default_ordering) {
+                    ([ordering], _) => ordering.as_str(),
+                    ([], Some(ordering)) if !no_default_order && !ordering.contains(',') => {
+                        ordering.as_str()
+                    }
+                    _ => return Err(Error::AfterWithoutSingleOrdering),
+                };
+                let Some(unique) = unique_column(&system_table_name) else {
+                    return Err(Error::AfterWithoutUniqueColumn(system_table_name));
+                };
+                let (column, descending) = split_ordering(ordering);
+                let literal = |column: &str, value: &str| {
+                    let data_type = columns
+                        .iter()
+                        .find(|c| c.column_name.eq_ignore_ascii_case(column))
+                        .map_or("", |c| c.data_type.as_str());
+                    sql_literal(value, data_type)
+                };
+                let op = if descending { "<" } else { ">" };
+                let after = literal(column, after);
+                // entries that share the ordering value are ordered by the unique column, so that
+                // a page can end part way through them without the rest being skipped
+                let tiebreak = (!column.eq_ignore_ascii_case(unique)).then_some(unique);
+                filters.push(match (tiebreak, &after_key) {
+                    (Some(tiebreak), Some(key)) => format!(
+                        "({column} {op} {after} OR ({column} = {after} AND {tiebreak} {op} {}))",
+                        literal(tiebreak, key)
+                    ),
+                    _ => format!("{column} {op} {after}"),
+                });
+                Some(Keyset {
+                    column: column.to_string(),
+                    tiebreak,
+                    descending,
+                })
+            }
+            None => None,
+        };
+
+        if count_only {
+            let start = Instant::now();
+            let count = self
+                .count_rows(&system_table_name, where_clause(&filters))
+                .await?;
+            if timing {
+                eprintln!("query took {:?}", start.elapsed());
+            }
+            let output = match output_format {
+                Format::Json | Format::JsonLines => {
+                    serde_json::json!({ "count": count }).to_string()
+                }
+                Format::Pretty | Format::Csv | Format::Parquet => count.to_string(),
+            };
+            match output_file_path {
+                Some(path) => tokio::fs::write(path, output).await?,
+                None => println!("{output}"),
+            }
+            return Ok(());
+        }
+
+        if let Some(where_clause) = where_clause(&filters) {
+            clauses.push(where_clause);
+        }
+
+        if group_by.is_some() {
+            clauses.push("GROUP BY time_bucket".to_string());
+            clauses.push("ORDER BY time_bucket".to_string());
+        } else if let Some(Keyset {
+            column,
+            tiebreak: Some(tiebreak),
+            descending,
+        }) = &keyset
+        {
+            let direction = if *descending { "DESC" } else { "ASC" };
+            clauses.push(format!(
+                "ORDER BY {column} {direction}, {tiebreak} {direction}"
+            ));
+        } else if !order_by.is_empty() {
             clauses.push(format!("ORDER BY {}", order_by.join(",")));
-        } else if let Some(default_ordering) = default_ordering(&system_table_name) {
+        } else if no_default_order {
+            debug!(table = %system_table_name, "default ordering disabled");
+        } else if let Some(default_ordering) = default_ordering {
+            debug!(
+                table = %system_table_name,
+                ordering = %default_ordering,
+                "using default ordering"
+            );
             clauses.push(format!("ORDER BY {default_ordering}"));
         }
 
@@ -202,29 +974,338 @@ impl SystemCommandRunner {
             clauses.push(format!("LIMIT {limit}"));
         }
 
+        // the next page is only looked up for the entries themselves, not for a query plan. The
+        // keyset columns are selected under their own names, as they may not be among the
+        // selected columns, and are removed from the output
+        let keyset = keyset.filter(|_| !explain && !explain_analyze && limit > 0);
+        if let Some(Keyset {
+            column, tiebreak, ..
+        }) = &keyset
+        {
+            let tiebreak = tiebreak
+                .map(|tiebreak| format!(", {tiebreak} AS {AFTER_KEY_COLUMN}"))
+                .unwrap_or_default();
+            clauses[0] = format!(
+                "SELECT {select_expr}, {column} AS {AFTER_COLUMN}{tiebreak} \
+                FROM system.\"{system_table_name}\""
+            );
+        }
+
+        if explain {
+            clauses.insert(0, "EXPLAIN".to_string());
+        } else if explain_analyze {
+            clauses.insert(0, "EXPLAIN ANALYZE".to_string());
+        }
+
         let query = clauses.join("\n");
 
-        let bs = match client
-            .api_v3_query_sql(db, query)
-            .format(output_format.into())
-            .send()
-            .await
-        {
-            Ok(bs) => bs,
+        let start = Instant::now();
+        let last = if keyset.is_some() {
+            self.query_page(&query, limit, output_format, output_file_path.as_deref())
+                .await?
+        } else {
+            if let Some(path) = output_file_path {
+                self.query_to_file(&query, output_format, &path).await?;
+            } else {
+                let bs = self
+                    .with_retries(|| {
+                        client
+                            .api_v3_query_sql(db, query.as_str())
+                            .format(output_format.into())
+                            .send()
+                    })
+                    .await?;
+                println!("{}", std::str::from_utf8(&bs)?);
+            }
+            None
+        };
+        if timing {
+            eprintln!("query took {:?}", start.elapsed());
+        }
+
+        if let Some(last) = last {
+            match last.key {
+                Some(key) => eprintln!("next page: --after {} --after-key {key}", last.value),
+                None => eprintln!("next page: --after {}", last.value),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the results of `query` to the file at `path`
+    ///
+    /// The results are streamed to the file so that large tables are not buffered in memory.
+    /// They are written to a temporary file alongside `path` that is only renamed to `path` once
+    /// the results have been written in full, so a failed query does not leave a truncated file.
+    async fn query_to_file(&self, query: &str, format: Format, path: &Path) -> Result<()> {
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        match self.stream_to_file(query, format, &partial).await {
+            Ok(()) => Ok(tokio::fs::rename(&partial, path).await?),
             Err(e) => {
-                if matches!(e, influxdb3_client::Error::ApiError { ref message, .. } if message.contains("not found"))
+                if let Err(error) = tokio::fs::remove_file(&partial).await
+                    && error.kind() != std::io::ErrorKind::NotFound
                 {
-                    let system_tables = self.get_system_tables().await?;
-                    return Err(Error::SystemTableNotFound(
-                        system_table_name.to_string(),
-                        SystemTableNotFound { system_tables },
-                    ));
+                    warn!(%error, path = %partial.display(), "failed to remove partial output");
                 }
-                return Err(e.into());
+                Err(e)
             }
+        }
+    }
+
+    async fn stream_to_file(&self, query: &str, format: Format, path: &Path) -> Result<()> {
+        let mut stream = self
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), query)
+                    .format(format.into())
+                    .send_stream()
+            })
+            .await?;
+        let mut f = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .await?;
+        while let Some(mut chunk) = stream.try_next().await? {
+            f.write_all_buf(&mut chunk).await?;
+        }
+        f.flush().await?;
+        Ok(())
+    }
+
+    /// Output a page of results of `query`, and get the values of the keyset columns in its last
+    /// entry if the page is full, meaning that there may be a next page
+    ///
+    /// The page is fetched as Parquet so that the keyset columns can be read from the record
+    /// batches, which are then written out in `format` as the server would have.
+    async fn query_page(
+        &self,
+        query: &str,
+        limit: u16,
+        format: Format,
+        path: Option<&Path>,
+    ) -> Result<Option<LastInPage>> {
+        let bs = self
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), query)
+                    .format(Format::Parquet.into())
+                    .send()
+            })
+            .await?;
+        // the server sends an empty body, rather than a Parquet file, when there are no entries
+        let batches = if bs.is_empty() {
+            vec![]
+        } else {
+            ParquetRecordBatchReaderBuilder::try_new(bs)?
+                .build()?
+                .collect::<std::result::Result<Vec<_>, _>>()?
         };
 
-        println!("{}", String::from_utf8(bs.as_ref().to_vec()).unwrap());
+        let rows = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+        let last = match batches.iter().rev().find(|batch| batch.num_rows() > 0) {
+            Some(batch) if rows >= usize::from(limit) => {
+                let row = batch.num_rows() - 1;
+                keyset_value(batch, AFTER_COLUMN, row)?.map(|value| {
+                    keyset_value(batch, AFTER_KEY_COLUMN, row).map(|key| LastInPage { value, key })
+                })
+            }
+            _ => None,
+        }
+        .transpose()?;
+
+        let batches = batches
+            .iter()
+            .map(|batch| {
+                let schema = batch.schema();
+                let indices = schema
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, field)| {
+                        ![AFTER_COLUMN, AFTER_KEY_COLUMN].contains(&field.name().as_str())
+                    })
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                batch.project(&indices)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let output = format_batches(&batches, format)?;
+        match path {
+            Some(path) => tokio::fs::write(path, output).await?,
+            None => println!("{}", std::str::from_utf8(&output)?),
+        }
+        Ok(last)
+    }
+}
+
+/// The names that the keyset columns are selected under for a page of entries
+const AFTER_COLUMN: &str = "next_page_after";
+const AFTER_KEY_COLUMN: &str = "next_page_after_key";
+
+/// The columns that the entries are paged through by with `--after`
+#[derive(Debug)]
+struct Keyset {
+    /// The column that the entries are ordered by
+    column: String,
+    /// The column that orders the entries which share a value of `column`, if it is not unique
+    tiebreak: Option<&'static str>,
+    descending: bool,
+}
+
+/// The values of the keyset columns in the last entry of a page
+#[derive(Debug)]
+struct LastInPage {
+    value: String,
+    key: Option<String>,
+}
+
+/// The value of `column` in `row` of `batch`, if it has one
+fn keyset_value(batch: &RecordBatch, column: &str, row: usize) -> Result<Option<String>> {
+    match batch.column_by_name(column) {
+        Some(array) if !array.is_null(row) => Ok(Some(array_value_to_string(array, row)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Write out `batches` in `format`, in the same way that the server writes query results
+fn format_batches(batches: &[RecordBatch], format: Format) -> Result<Vec<u8>> {
+    Ok(match format {
+        Format::Pretty => pretty_format_batches(batches)?.to_string().into_bytes(),
+        Format::Json => {
+            let mut writer = arrow::json::ArrayWriter::new(Vec::new());
+            writer.write_batches(&batches.iter().collect::<Vec<_>>())?;
+            writer.finish()?;
+            writer.into_inner()
+        }
+        Format::JsonLines => {
+            let mut writer = arrow::json::LineDelimitedWriter::new(Vec::new());
+            writer.write_batches(&batches.iter().collect::<Vec<_>>())?;
+            writer.finish()?;
+            writer.into_inner()
+        }
+        Format::Csv => {
+            let mut writer = arrow::csv::Writer::new(Vec::new());
+            for batch in batches {
+                writer.write(batch)?;
+            }
+            writer.into_inner()
+        }
+        Format::Parquet => {
+            let mut bytes = Vec::new();
+            if let Some(batch) = batches.first() {
+                let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None)?;
+                for batch in batches {
+                    writer.write(batch)?;
+                }
+                writer.close()?;
+            }
+            bytes
+        }
+    })
+}
+
+/// The extension for a file written in the given format
+fn file_extension(format: Format) -> &'static str {
+    match format {
+        Format::Pretty => "txt",
+        Format::Json => "json",
+        Format::JsonLines => "jsonl",
+        Format::Csv => "csv",
+        Format::Parquet => "parquet",
+    }
+}
+
+/// Deserialize the JSON rows returned by the server for a query about the system tables,
+/// described by `what` in the error if they are not in the expected form
+fn deserialize_rows<T: DeserializeOwned>(bs: &[u8], what: &'static str) -> Result<Vec<T>> {
+    serde_json::from_slice(bs).map_err(|source| Error::UnexpectedSchema { what, source })
+}
+
+/// Split an `ORDER BY` expression into the column and whether it is in descending order
+fn split_ordering(expr: &str) -> (&str, bool) {
+    match expr.trim().rsplit_once(char::is_whitespace) {
+        Some((column, direction)) if direction.eq_ignore_ascii_case("asc") => {
+            (column.trim_end(), false)
+        }
+        Some((column, direction)) if direction.eq_ignore_ascii_case("desc") => {
+            (column.trim_end(), true)
+        }
+        _ => (expr.trim(), false),
+    }
+}
+
+/// Format a value given on the command line as a SQL literal for a column of the given type
+///
+/// Numbers are left as they are for numeric columns, anything else is quoted as a string, which
+/// DataFusion coerces to the column type, e.g., for timestamps.
+fn sql_literal(value: &str, data_type: &str) -> String {
+    let numeric = ["Int", "UInt", "Float", "Decimal"]
+        .iter()
+        .any(|prefix| data_type.starts_with(prefix));
+    let boolean = data_type == "Boolean" && value.parse::<bool>().is_ok();
+    if (numeric && value.parse::<f64>().is_ok()) || boolean {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[derive(Debug, Parser)]
+pub(super) struct DescribeConfig {
+    /// The system table to describe.
+    system_table: String,
+
+    #[clap(flatten)]
+    client_config: ClientConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct DescribeOutput<'a> {
+    table_name: &'a str,
+    columns: Vec<ShowColumnsRow>,
+}
+
+impl SystemCommandRunner {
+    async fn get_table_columns(&self, table_name: &str) -> Result<Vec<ShowColumnsRow>> {
+        let bs = self
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), SYS_TABLE_COLUMNS_QUERY)
+                    .with_param("table_name", table_name)
+                    .format(Format::Json.into())
+                    .send()
+            })
+            .await?;
+
+        deserialize_rows(&bs, "system table columns")
+    }
+
+    async fn describe(&self, config: DescribeConfig) -> Result<()> {
+        let DescribeConfig {
+            system_table: system_table_name,
+            ..
+        } = config;
+
+        let columns = self.get_table_columns(&system_table_name).await?;
+        if columns.is_empty() {
+            let system_tables = self.get_system_tables().await?;
+            let not_found = SystemTableNotFound::new(system_tables, &system_table_name);
+            return Err(Error::UnknownTable(system_table_name, not_found));
+        }
+
+        let output = DescribeOutput {
+            table_name: &system_table_name,
+            columns,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).map_err(Error::SerializingOutput)?
+        );
 
         Ok(())
     }
@@ -237,37 +1318,213 @@ pub(super) struct SummaryConfig {
     #[clap(long = "limit", short = 'l', default_value_t = 10)]
     limit: u16,
 
+    /// Re-run the summary every given number of seconds until interrupted with Ctrl-C.
+    #[clap(long = "watch", short = 'w', value_parser = clap::value_parser!(u64).range(1..))]
+    watch: Option<u64>,
+
+    /// The maximum number of system tables to query concurrently.
+    #[clap(
+        long = "concurrency",
+        default_value_t = 4,
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    concurrency: u16,
+
+    /// Only summarize the specified system tables.
+    #[clap(long = "only", num_args = 1, value_delimiter = ',')]
+    only: Vec<String>,
+
+    /// Do not summarize the specified system tables, e.g., `--exclude queries`.
+    #[clap(long = "exclude", num_args = 1, value_delimiter = ',')]
+    exclude: Vec<String>,
+
     /// The format in which to output the query
     #[clap(value_enum, long = "format", default_value = "pretty")]
     output_format: Format,
 
-    /// An optional arg to use a custom ca for useful for testing with self signed certs
-    #[clap(long = "tls-ca", env = "INFLUXDB3_TLS_CA")]
-    ca_cert: Option<PathBuf>,
+    /// Print how long the queries for each table, and for all of the tables, took to stderr, so
+    /// that it is kept apart from the output.
+    #[clap(long = "timing", default_value_t = false)]
+    timing: bool,
+
+    /// Write the full contents of each system table to its own file in this directory, named
+    /// after the table with an extension for the `--format`, e.g., `queries.csv`, rather than
+    /// printing a summary. `--limit` is not applied, and the `parquet` format can be used. When
+    /// several databases are given, each one is written to a subdirectory named after it.
+    #[clap(long = "output-dir", conflicts_with = "watch")]
+    output_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    client_config: ClientConfig,
 }
 
 impl SystemCommandRunner {
     async fn summary(&self, config: SummaryConfig) -> Result<()> {
-        self.summarize_all_tables(config.limit, config.output_format)
-            .await?;
+        if config.output_format.is_parquet() && config.output_dir.is_none() {
+            return Err(Error::ParquetNotSupported);
+        }
+        let Some(interval) = config.watch.map(Duration::from_secs) else {
+            return self.summarize_all_databases(&config).await;
+        };
+
+        tokio::select! {
+            res = self.watch_all_tables(interval, &config) => res,
+            _ = tokio::signal::ctrl_c() => Ok(()),
+        }
+    }
+
+    /// Repeatedly summarize all tables on the given `interval`
+    ///
+    /// The screen is cleared between iterations when writing pretty output to a terminal,
+    /// otherwise a separator line is printed so that the output can still be piped.
+    async fn watch_all_tables(&self, interval: Duration, config: &SummaryConfig) -> Result<()> {
+        let clear_screen =
+            matches!(config.output_format, Format::Pretty) && std::io::stdout().is_terminal();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut first = true;
+        loop {
+            ticker.tick().await;
+            if clear_screen {
+                print!("\x1B[2J\x1B[1;1H");
+            } else if !first {
+                println!("---");
+            }
+            first = false;
+            self.summarize_all_databases(config).await?;
+        }
+    }
+
+    /// Summarize each of the databases given as a comma-separated list to `--database`
+    ///
+    /// When there is more than one database, each database's summary is preceded by a header
+    /// line, except for `json` output, where each table summary includes its database.
+    async fn summarize_all_databases(&self, config: &SummaryConfig) -> Result<()> {
+        let databases = self
+            .db
+            .split(',')
+            .map(str::trim)
+            .filter(|db| !db.is_empty())
+            .collect::<Vec<_>>();
+        if let [db] = databases.as_slice()
+            && *db == self.db
+        {
+            return match &config.output_dir {
+                Some(dir) => self.export_all_tables(config, dir).await,
+                None => self.summarize_all_tables(config).await,
+            };
+        }
+        for db in databases {
+            let runner = self.with_db(db);
+            if let Some(dir) = &config.output_dir {
+                runner.export_all_tables(config, &dir.join(db)).await?;
+                continue;
+            }
+            if !matches!(config.output_format, Format::Json) {
+                println!("database {db}:");
+            }
+            runner.summarize_all_tables(config).await?;
+        }
         Ok(())
     }
 
-    async fn summarize_all_tables(&self, limit: u16, format: Format) -> Result<()> {
-        let system_tables = self.get_system_tables().await?;
-        for table in system_tables {
-            self.summarize_table(table.table_name.as_str(), limit, format)
-                .await?;
+    /// Write the full contents of each system table to its own file in `dir`, issuing up to
+    /// `config.concurrency` queries at a time
+    ///
+    /// The path of each file is printed once it has been written, in the order the tables were
+    /// listed by the server. Tables are restricted by `--only` and `--exclude`, if given.
+    async fn export_all_tables(&self, config: &SummaryConfig, dir: &Path) -> Result<()> {
+        let SummaryConfig {
+            output_format,
+            concurrency,
+            timing,
+            ..
+        } = *config;
+        let start = Instant::now();
+        let system_tables = filter_tables(
+            self.get_system_tables().await?,
+            &config.only,
+            &config.exclude,
+        );
+        tokio::fs::create_dir_all(dir).await?;
+        let mut exports = futures::stream::iter(system_tables)
+            .map(|table| async move {
+                let start = Instant::now();
+                let table_name = table.table_name;
+                let path = dir.join(format!("{table_name}.{}", file_extension(output_format)));
+                let query = std::iter::once(format!("SELECT * FROM system.\"{table_name}\""))
+                    .chain(default_ordering(&table_name).map(|o| format!("ORDER BY {o}")))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let res = self.query_to_file(&query, output_format, &path).await;
+                (path, res, start.elapsed())
+            })
+            .buffered(usize::from(concurrency));
+        while let Some((path, res, elapsed)) = exports.next().await {
+            res?;
+            println!("{}", path.display());
+            if timing {
+                eprintln!("{} took {elapsed:?}", path.display());
+            }
+        }
+        if timing {
+            eprintln!("all tables took {:?}", start.elapsed());
+        }
+        Ok(())
+    }
+
+    /// Summarize all system tables, issuing up to `config.concurrency` queries at a time
+    ///
+    /// Summaries are printed in the order the tables were listed by the server, regardless of
+    /// the order in which the queries complete. Tables are restricted by `--only` and
+    /// `--exclude`, if given.
+    async fn summarize_all_tables(&self, config: &SummaryConfig) -> Result<()> {
+        let SummaryConfig {
+            limit,
+            output_format,
+            concurrency,
+            timing,
+            ..
+        } = *config;
+        let start = Instant::now();
+        let system_tables = filter_tables(
+            self.get_system_tables().await?,
+            &config.only,
+            &config.exclude,
+        );
+        let mut summaries = futures::stream::iter(system_tables)
+            .map(|table| async move {
+                let start = Instant::now();
+                let summary = self
+                    .summarize_table(table.table_name.as_str(), limit, output_format)
+                    .await;
+                (table.table_name, summary, start.elapsed())
+            })
+            .buffered(usize::from(concurrency));
+        while let Some((table_name, summary, elapsed)) = summaries.next().await {
+            println!("{}", summary?);
+            if timing {
+                eprintln!("{table_name} took {elapsed:?}");
+            }
+        }
+        if timing {
+            eprintln!("all tables took {:?}", start.elapsed());
         }
         Ok(())
     }
 
-    async fn summarize_table(&self, table_name: &str, limit: u16, format: Format) -> Result<()> {
-        let Self { db, client } = self;
+    async fn summarize_table(
+        &self,
+        table_name: &str,
+        limit: u16,
+        format: Format,
+    ) -> Result<String> {
+        let Self { db, client, .. } = self;
+        let where_clause = default_filter(table_name).map(|f| format!("WHERE {f}"));
         let mut clauses = vec![format!("SELECT * FROM system.\"{table_name}\"")];
 
-        if let Some(default_filter) = default_filter(table_name) {
-            clauses.push(format!("WHERE {default_filter}"));
+        if let Some(where_clause) = &where_clause {
+            clauses.push(where_clause.clone());
         }
 
         if let Some(default_ordering) = default_ordering(table_name) {
@@ -280,16 +1537,93 @@ impl SystemCommandRunner {
 
         let query = clauses.join("\n");
 
-        let bs = client
-            .api_v3_query_sql(db, query)
-            .format(format.into())
-            .send()
+        let count = self.count_rows(table_name, where_clause).await?;
+
+        let bs = self
+            .with_retries(|| {
+                client
+                    .api_v3_query_sql(db, query.as_str())
+                    .format(format.into())
+                    .send()
+            })
+            .await?;
+
+        if let Format::Json = format {
+            let rows = serde_json::from_slice::<serde_json::Value>(bs.as_ref())
+                .map_err(Error::DeserializingSummary)?;
+            let summary = TableSummary {
+                database: db,
+                table_name,
+                total_rows: count,
+                rows,
+            };
+            return serde_json::to_string(&summary).map_err(Error::SerializingOutput);
+        }
+
+        Ok(format!(
+            "{table_name} summary ({count} rows):\n{}",
+            std::str::from_utf8(&bs)?
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CountRow {
+    count: u64,
+}
+
+impl SystemCommandRunner {
+    /// Count the entries in a system table that match the optional `WHERE` clause
+    async fn count_rows(&self, table_name: &str, where_clause: Option<String>) -> Result<u64> {
+        let query = std::iter::once(format!(
+            "SELECT COUNT(*) AS count FROM system.\"{table_name}\""
+        ))
+        .chain(where_clause)
+        .collect::<Vec<_>>()
+        .join("\n");
+        let bs = self
+            .with_retries(|| {
+                self.client
+                    .api_v3_query_sql(self.db.as_str(), query.as_str())
+                    .format(Format::Json.into())
+                    .send()
+            })
             .await?;
+        Ok(serde_json::from_slice::<Vec<CountRow>>(bs.as_ref())
+            .map_err(Error::DeserializingRowCount)?
+            .first()
+            .map_or(0, |row| row.count))
+    }
+}
 
-        println!("{table_name} summary:");
-        println!("{}", String::from_utf8(bs.as_ref().to_vec()).unwrap());
-        Ok(())
+/// The summary of a system table, as output by `summary --format json`
+#[derive(Debug, Serialize)]
+struct TableSummary<'a> {
+    database: &'a str,
+    table_name: &'a str,
+    /// The total number of rows in the table, which may be more than are included in `rows`
+    total_rows: u64,
+    rows: serde_json::Value,
+}
+
+/// Keep only the tables named in `only`, if it is not empty, and drop those named in `exclude`
+///
+/// A warning is logged for any name that does not match a system table, as it is likely a typo.
+fn filter_tables(
+    tables: Vec<ShowTablesRow>,
+    only: &[String],
+    exclude: &[String],
+) -> Vec<ShowTablesRow> {
+    for name in only.iter().chain(exclude) {
+        if !tables.iter().any(|t| &t.table_name == name) {
+            warn!(table = %name, "ignoring unknown system table");
+        }
     }
+    tables
+        .into_iter()
+        .filter(|t| only.is_empty() || only.contains(&t.table_name))
+        .filter(|t| !exclude.contains(&t.table_name))
+        .collect()
 }
 
 fn default_ordering(table_name: &str) -> Option<String> {
@@ -299,11 +1633,156 @@ fn default_ordering(table_name: &str) -> Option<String> {
         "parquet_files" => Some("size_bytes"),
         "queries" => Some("end2end_duration"),
         "distinct_caches" => Some("max_cardinality"),
+        "databases" => Some("database_name"),
+        "generation_durations" => Some("level"),
+        "influxdb_schema" => Some("measurement,key"),
+        "nodes" => Some("node_id"),
+        "plugin_files" => Some("plugin_name"),
+        "processing_engine_logs" => Some("event_time"),
+        "processing_engine_trigger_arguments" => Some("trigger_name,argument_key"),
+        "processing_engine_triggers" => Some("trigger_name"),
+        "tables" => Some("database_name,table_name"),
+        "tokens" => Some("token_id"),
         _ => None,
     }
     .map(ToString::to_string)
 }
 
+/// Parse the duration of the buckets for `--group-by-interval`
+fn parse_interval(s: &str) -> Result<Duration, String> {
+    match humantime::parse_duration(s) {
+        Ok(interval) if interval.is_zero() => Err("the interval must not be zero".to_string()),
+        Ok(interval) => Ok(interval),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The expression that puts each entry in a bucket of the given duration by its time column
+///
+/// The interval is given in nanoseconds, so that durations of less than a millisecond, or with a
+/// fraction of one, are not truncated.
+fn time_bucket(interval: Duration, time_column: &str) -> String {
+    format!(
+        "date_bin(INTERVAL '{} nanoseconds', {time_column}) AS time_bucket",
+        interval.as_nanos()
+    )
+}
+
+/// The column that identifies each entry of a system table, which `--after` pages by
+fn unique_column(table_name: &str) -> Option<&'static str> {
+    match table_name {
+        "databases" => Some("database_name"),
+        "nodes" => Some("node_id"),
+        "parquet_files" => Some("path"),
+        "plugin_files" => Some("file_path"),
+        "processing_engine_triggers" => Some("trigger_name"),
+        "queries" => Some("id"),
+        "tokens" => Some("token_id"),
+        _ => None,
+    }
+}
+
+/// An aggregate function that can be applied with `--agg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "snake_case")]
+pub(crate) enum Aggregate {
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+}
+
+impl std::fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Sum => "sum",
+            Self::Count => "count",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The column, or expression, to use when filtering the given system table by time
+fn time_column(table_name: &str) -> Option<&'static str> {
+    match table_name {
+        "queries" => Some("issue_time"),
+        "parquet_files" => Some("to_timestamp_nanos(max_time)"),
+        "processing_engine_logs" => Some("event_time"),
+        "tokens" => Some("created_at"),
+        "nodes" => Some("updated_at"),
+        _ => None,
+    }
+}
+
+/// A bound for filtering system table entries by time, as passed to `--since` or `--until`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeBound {
+    /// An absolute point in time
+    Absolute(DateTime<Utc>),
+    /// A duration before the time the command is run
+    Relative(Duration),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "'{0}' is not a valid time, use an RFC3339 timestamp, e.g., 2025-01-01T00:00:00Z, or a \
+    relative duration, e.g., 1h"
+)]
+struct ParseTimeBoundError(String);
+
+impl FromStr for TimeBound {
+    type Err = ParseTimeBoundError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Self::Absolute(t.with_timezone(&Utc)));
+        }
+        humantime::parse_duration(s)
+            .map(Self::Relative)
+            .map_err(|_| ParseTimeBoundError(s.to_string()))
+    }
+}
+
+impl TimeBound {
+    /// Resolve the bound relative to `now` and produce a SQL timestamp expression
+    fn to_sql(self, now: DateTime<Utc>) -> String {
+        let t = match self {
+            Self::Absolute(t) => t,
+            Self::Relative(d) => chrono::Duration::from_std(d)
+                .ok()
+                .and_then(|d| now.checked_sub_signed(d))
+                .unwrap_or(DateTime::UNIX_EPOCH),
+        };
+        format!(
+            "to_timestamp('{}')",
+            t.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        )
+    }
+}
+
+/// Combine `filters` into a single `WHERE` clause, or `None` if there are no filters
+///
+/// Each filter is parenthesized when there is more than one so that operator precedence within
+/// an individual predicate is preserved when they are joined with `AND`.
+fn where_clause(filters: &[String]) -> Option<String> {
+    match filters {
+        [] => None,
+        [filter] => Some(format!("WHERE {filter}")),
+        filters => Some(format!(
+            "WHERE {}",
+            filters
+                .iter()
+                .map(|f| format!("({f})"))
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        )),
+    }
+}
+
 fn default_filter(table_name: &str) -> Option<String> {
     match table_name {
         "queries" => Some("query_text !~ '.*(select.queries.|information_schema)*'"),
@@ -311,3 +1790,237 @@ fn default_filter(table_name: &str) -> Option<String> {
     }
     .map(ToString::to_string)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+
+    use reqwest::StatusCode;
+
+    use super::{
+        Error, ShowColumnsRow, ShowTablesRow, SystemTableNotFound, TableListColumn, TableListEntry,
+        TimeBound, deserialize_rows, edit_distance, filter_tables, grep_columns, is_transient,
+        parse_interval, split_ordering, sql_literal, time_bucket, validate_columns,
+    };
+
+    fn system_tables(names: &[&str]) -> Vec<ShowTablesRow> {
+        names
+            .iter()
+            .map(|n| ShowTablesRow {
+                table_name: n.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(0, edit_distance("queries", "queries"));
+        assert_eq!(1, edit_distance("queries", "querie"));
+        assert_eq!(2, edit_distance("queries", "querys"));
+        assert_eq!(3, edit_distance("", "cpu"));
+    }
+
+    #[test]
+    fn test_system_table_not_found_suggestion() {
+        let tables = system_tables(&["last_caches", "parquet_files", "queries"]);
+        let not_found = SystemTableNotFound::new(tables, "querys");
+        assert_eq!(Some("queries"), not_found.suggestion.as_deref());
+        assert!(not_found.to_string().starts_with("did you mean 'queries'?"));
+
+        let tables = system_tables(&["last_caches", "parquet_files", "queries"]);
+        let not_found = SystemTableNotFound::new(tables, "meow");
+        assert!(not_found.suggestion.is_none());
+        assert!(
+            not_found
+                .to_string()
+                .starts_with("please use a valid system table name")
+        );
+    }
+
+    #[test]
+    fn test_filter_tables() {
+        let names = |tables: Vec<ShowTablesRow>| {
+            tables.into_iter().map(|t| t.table_name).collect::<Vec<_>>()
+        };
+        let tables = || system_tables(&["cpu", "parquet_files", "queries"]);
+        let strings = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec!["cpu", "parquet_files", "queries"],
+            names(filter_tables(tables(), &[], &[]))
+        );
+        assert_eq!(
+            vec!["cpu", "parquet_files"],
+            names(filter_tables(tables(), &[], &strings(&["queries", "meow"])))
+        );
+        assert_eq!(
+            vec!["queries"],
+            names(filter_tables(tables(), &strings(&["queries", "meow"]), &[]))
+        );
+        assert_eq!(
+            vec!["cpu"],
+            names(filter_tables(
+                tables(),
+                &strings(&["cpu", "queries"]),
+                &strings(&["queries"])
+            ))
+        );
+    }
+
+    #[test]
+    fn test_grep_columns() {
+        let table = |name: &str, columns: &[&str]| TableListEntry {
+            table: name.to_string(),
+            columns: columns
+                .iter()
+                .map(|c| TableListColumn {
+                    name: c.to_string(),
+                    data_type: "Utf8".to_string(),
+                })
+                .collect(),
+        };
+        let tables = vec![
+            table("queries", &["end2end_duration", "query_text"]),
+            table("parquet_files", &["size_bytes", "Duration_ms"]),
+            table("cpu", &["usage"]),
+        ];
+
+        let grepped = grep_columns(tables, "DURATION")
+            .into_iter()
+            .map(|t| (t.table, t.columns.into_iter().map(|c| c.name).collect()))
+            .collect::<Vec<(String, Vec<String>)>>();
+        assert_eq!(
+            vec![
+                ("queries".to_string(), vec!["end2end_duration".to_string()]),
+                ("parquet_files".to_string(), vec!["Duration_ms".to_string()]),
+                ("cpu".to_string(), vec![]),
+            ],
+            grepped
+        );
+    }
+
+    #[test]
+    fn test_validate_columns() {
+        let columns = ["query_text", "success", "end2end_duration"]
+            .into_iter()
+            .map(|name| ShowColumnsRow {
+                column_name: name.to_string(),
+                data_type: "Utf8".to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        validate_columns("queries", &columns, ["query_text", "success"]).unwrap();
+        validate_columns(
+            "queries",
+            &columns,
+            ["end2end_duration DESC", "Success asc"],
+        )
+        .unwrap();
+        validate_columns("queries", &columns, ["*", "count(*)", "\"query_text\""]).unwrap();
+
+        let err = validate_columns("queries", &columns, ["query_text", "sucess"]).unwrap_err();
+        let Error::UnknownColumn {
+            column, not_found, ..
+        } = &err
+        else {
+            panic!("expected an unknown column error, got: {err}");
+        };
+        assert_eq!("sucess", column);
+        assert_eq!(Some("success"), not_found.suggestion.as_deref());
+
+        let err = validate_columns("queries", &columns, ["meow desc"]).unwrap_err();
+        assert_eq!(
+            "system table 'queries' has no column 'meow': valid columns are: \
+            [\"query_text\", \"success\", \"end2end_duration\"]",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_keyset_paging() {
+        assert_eq!(("size_bytes", false), split_ordering("size_bytes"));
+        assert_eq!(("size_bytes", false), split_ordering(" size_bytes ASC"));
+        assert_eq!(("size_bytes", true), split_ordering("size_bytes  desc"));
+
+        assert_eq!("1024", sql_literal("1024", "Int64"));
+        assert_eq!("1.5", sql_literal("1.5", "Float64"));
+        assert_eq!("'1024'", sql_literal("1024", "Utf8"));
+        assert_eq!("'o''brien'", sql_literal("o'brien", "Utf8"));
+        assert_eq!("false", sql_literal("false", "Boolean"));
+        assert_eq!("'no'", sql_literal("no", "Boolean"));
+        assert_eq!(
+            "'2025-01-01T00:00:00Z'",
+            sql_literal("2025-01-01T00:00:00Z", "Timestamp(Nanosecond, None)")
+        );
+        // a value that is not a number is quoted even for a numeric column, so the server
+        // reports the mismatch rather than it being injected into the query
+        assert_eq!("'1 OR true'", sql_literal("1 OR true", "Int64"));
+    }
+
+    #[test]
+    fn test_time_bucket() {
+        assert_eq!(
+            "date_bin(INTERVAL '60000000000 nanoseconds', issue_time) AS time_bucket",
+            time_bucket(parse_interval("1m").unwrap(), "issue_time")
+        );
+        // intervals of less than a millisecond are not truncated to zero
+        assert_eq!(
+            "date_bin(INTERVAL '100000 nanoseconds', issue_time) AS time_bucket",
+            time_bucket(parse_interval("100us").unwrap(), "issue_time")
+        );
+        assert_eq!(
+            "date_bin(INTERVAL '1500000 nanoseconds', issue_time) AS time_bucket",
+            time_bucket(Duration::from_micros(1500), "issue_time")
+        );
+
+        let err = parse_interval("0s").unwrap_err();
+        assert_eq!("the interval must not be zero", err);
+        assert!(parse_interval("1 fortnight").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        let now: DateTime<Utc> = "2025-01-01T12:00:00Z".parse().unwrap();
+
+        let absolute: TimeBound = "2025-01-01T00:00:00+01:00".parse().unwrap();
+        assert_eq!("to_timestamp('2024-12-31T23:00:00Z')", absolute.to_sql(now));
+
+        let relative: TimeBound = "1h 30m".parse().unwrap();
+        assert_eq!(TimeBound::Relative(Duration::from_secs(5_400)), relative);
+        assert_eq!("to_timestamp('2025-01-01T10:30:00Z')", relative.to_sql(now));
+
+        assert!("yesterday".parse::<TimeBound>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rows() {
+        let tables =
+            deserialize_rows::<ShowTablesRow>(br#"[{"table_name": "queries"}]"#, "system tables")
+                .unwrap();
+        assert_eq!("queries", tables[0].table_name);
+
+        let err = deserialize_rows::<ShowTablesRow>(br#"[{"name": "queries"}]"#, "system tables")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnexpectedSchema {
+                what: "system tables",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_is_transient() {
+        let api_error = |code| influxdb3_client::Error::ApiError {
+            code,
+            message: String::new(),
+        };
+        assert!(is_transient(&api_error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(is_transient(&api_error(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(!is_transient(&api_error(StatusCode::BAD_REQUEST)));
+        assert!(!is_transient(&api_error(StatusCode::UNAUTHORIZED)));
+    }
+}
@@ -62,7 +62,8 @@ pub struct Config {
     /// Give a quoted line protocol line via the command line
     line_protocol: Option<Vec<String>>,
 
-    /// Specify a supported precision (eg: auto, ns, us, ms, s).
+    /// Specify a supported precision (eg: auto, ns, us, ms, s). Defaults to auto, which infers
+    /// the unit of each timestamp from its magnitude.
     #[clap(short = 'p', long = "precision")]
     precision: Option<Precision>,
 
@@ -1182,6 +1182,9 @@ pub struct ShowSystemQuery<'a> {
 #[derive(Debug)]
 pub struct ShowSystemTableListQuery<'a> {
     base: ShowSystemQuery<'a>,
+    types: bool,
+    names_only: bool,
+    grep: Option<String>,
 }
 
 // Specific struct for "table" subcommand
@@ -1192,6 +1195,21 @@ pub struct ShowSystemTableQuery<'a> {
     limit: Option<usize>,
     order_by: Option<String>,
     select: Option<String>,
+    where_filters: Vec<String>,
+    after: Option<String>,
+    after_key: Option<String>,
+    group_by_interval: Option<String>,
+    agg: Option<String>,
+    output: Option<String>,
+    count_only: bool,
+    explain: Option<&'static str>,
+}
+
+// Specific struct for "describe" subcommand
+#[derive(Debug)]
+pub struct ShowSystemDescribeQuery<'a> {
+    base: ShowSystemQuery<'a>,
+    system_table: String,
 }
 
 // Specific struct for "summary" subcommand
@@ -1199,6 +1217,8 @@ pub struct ShowSystemTableQuery<'a> {
 pub struct ShowSystemSummaryQuery<'a> {
     base: ShowSystemQuery<'a>,
     limit: Option<usize>,
+    concurrency: Option<u16>,
+    output_dir: Option<String>,
 }
 
 impl TestServer {
@@ -1221,7 +1241,12 @@ impl<'a> ShowSystemQuery<'a> {
 
     // Branch to table-list subcommand
     pub fn table_list(self) -> ShowSystemTableListQuery<'a> {
-        ShowSystemTableListQuery { base: self }
+        ShowSystemTableListQuery {
+            base: self,
+            types: false,
+            names_only: false,
+            grep: None,
+        }
     }
 
     // Branch to table subcommand
@@ -1232,6 +1257,22 @@ impl<'a> ShowSystemQuery<'a> {
             limit: None,
             order_by: None,
             select: None,
+            where_filters: Vec::new(),
+            after: None,
+            after_key: None,
+            group_by_interval: None,
+            agg: None,
+            output: None,
+            count_only: false,
+            explain: None,
+        }
+    }
+
+    // Branch to describe subcommand
+    pub fn describe(self, system_table: impl Into<String>) -> ShowSystemDescribeQuery<'a> {
+        ShowSystemDescribeQuery {
+            base: self,
+            system_table: system_table.into(),
         }
     }
 
@@ -1240,11 +1281,31 @@ impl<'a> ShowSystemQuery<'a> {
         ShowSystemSummaryQuery {
             base: self,
             limit: None,
+            concurrency: None,
+            output_dir: None,
         }
     }
 }
 
 impl ShowSystemTableListQuery<'_> {
+    // Show column data types
+    pub fn with_types(mut self) -> Self {
+        self.types = true;
+        self
+    }
+
+    // Only list the table names
+    pub fn with_names_only(mut self) -> Self {
+        self.names_only = true;
+        self
+    }
+
+    // Only list the columns whose names contain the given text
+    pub fn with_grep(mut self, text: impl Into<String>) -> Self {
+        self.grep = Some(text.into());
+        self
+    }
+
     // Run the table-list command
     pub fn run(self) -> Result<String> {
         let mut args = vec![
@@ -1260,6 +1321,19 @@ impl ShowSystemTableListQuery<'_> {
             args.push(format);
         }
 
+        if self.types {
+            args.push("--types");
+        }
+
+        if self.names_only {
+            args.push("--names-only");
+        }
+
+        if let Some(grep) = &self.grep {
+            args.push("--grep");
+            args.push(grep);
+        }
+
         self.base.server.run(vec!["show", "system"], &args)
     }
 }
@@ -1283,6 +1357,60 @@ impl ShowSystemTableQuery<'_> {
         self
     }
 
+    // Add a where filter, can be called multiple times
+    pub fn with_where(mut self, filter: impl Into<String>) -> Self {
+        self.where_filters.push(filter.into());
+        self
+    }
+
+    // Only show entries after this value of the ordering column
+    pub fn with_after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    // The value of the unique column in the last entry of the previous page, to page by
+    pub fn with_after_key(mut self, after_key: impl Into<String>) -> Self {
+        self.after_key = Some(after_key.into());
+        self
+    }
+
+    // Aggregate the entries into buckets of the given duration
+    pub fn with_group_by_interval(mut self, interval: impl Into<String>) -> Self {
+        self.group_by_interval = Some(interval.into());
+        self
+    }
+
+    // Set the aggregate function used with the group by interval
+    pub fn with_agg(mut self, agg: impl Into<String>) -> Self {
+        self.agg = Some(agg.into());
+        self
+    }
+
+    // Write the output to a file
+    pub fn with_output(mut self, path: impl Into<String>) -> Self {
+        self.output = Some(path.into());
+        self
+    }
+
+    // Only output the number of matching entries
+    pub fn with_count_only(mut self) -> Self {
+        self.count_only = true;
+        self
+    }
+
+    // Output the query plan instead of the entries
+    pub fn with_explain(mut self) -> Self {
+        self.explain = Some("--explain");
+        self
+    }
+
+    // Output the query plan with execution metrics instead of the entries
+    pub fn with_explain_analyze(mut self) -> Self {
+        self.explain = Some("--explain-analyze");
+        self
+    }
+
     // Run the table command
     pub fn run(self) -> Result<String> {
         let mut args = vec![
@@ -1315,6 +1443,43 @@ impl ShowSystemTableQuery<'_> {
             args.push(select);
         }
 
+        for filter in &self.where_filters {
+            args.push("--where");
+            args.push(filter);
+        }
+
+        if let Some(after) = &self.after {
+            args.push("--after");
+            args.push(after);
+        }
+        if let Some(after_key) = &self.after_key {
+            args.push("--after-key");
+            args.push(after_key);
+        }
+
+        if let Some(interval) = &self.group_by_interval {
+            args.push("--group-by-interval");
+            args.push(interval);
+        }
+
+        if let Some(agg) = &self.agg {
+            args.push("--agg");
+            args.push(agg);
+        }
+
+        if let Some(output) = &self.output {
+            args.push("--output");
+            args.push(output);
+        }
+
+        if self.count_only {
+            args.push("--count-only");
+        }
+
+        if let Some(explain) = self.explain {
+            args.push(explain);
+        }
+
         // System table name is required
         args.push(&self.system_table);
 
@@ -1322,6 +1487,22 @@ impl ShowSystemTableQuery<'_> {
     }
 }
 
+impl ShowSystemDescribeQuery<'_> {
+    // Run the describe command
+    pub fn run(self) -> Result<String> {
+        let args = vec![
+            "--database",
+            &self.base.db_name,
+            "describe",
+            "--tls-ca",
+            "../testing-certs/rootCA.pem",
+            &self.system_table,
+        ];
+
+        self.base.server.run(vec!["show", "system"], &args)
+    }
+}
+
 impl ShowSystemSummaryQuery<'_> {
     // Set limit for summary entries
     pub fn with_limit(mut self, limit: usize) -> Self {
@@ -1329,6 +1510,18 @@ impl ShowSystemSummaryQuery<'_> {
         self
     }
 
+    // Set the number of tables to query at once
+    pub fn with_concurrency(mut self, concurrency: u16) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    // Write each table to a file in the directory instead of summarizing
+    pub fn with_output_dir(mut self, dir: impl Into<String>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
     // Run the summary command
     pub fn run(self) -> Result<String> {
         let mut args = vec![
@@ -1351,6 +1544,18 @@ impl ShowSystemSummaryQuery<'_> {
             args.push(limit.as_str());
         }
 
+        let concurrency = self.concurrency.unwrap_or_default().to_string();
+
+        if self.concurrency.is_some() {
+            args.push("--concurrency");
+            args.push(concurrency.as_str());
+        }
+
+        if let Some(output_dir) = &self.output_dir {
+            args.push("--output-dir");
+            args.push(output_dir);
+        }
+
         self.base.server.run(vec!["show", "system"], &args)
     }
 }
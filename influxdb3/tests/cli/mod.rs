@@ -86,6 +86,25 @@ fn test_telemetry_disabled_with_debug_msg() {
     assert_contains!(output, expected_disabled);
 }
 
+#[test_log::test]
+fn test_serve_rejects_combined_grpc_compression() {
+    // `none` turns compression off, so it makes no sense alongside an encoding
+    let output = cargo_bin_cmd!("influxdb3")
+        .args(["serve", "--node-id", "the-best-node", "--object-store"])
+        .args(["memory", "--grpc-compression", "none,gzip"])
+        .timeout(std::time::Duration::from_millis(5000))
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let output = String::from_utf8(output).expect("must be able to convert output to String");
+    assert_contains!(
+        output,
+        "none cannot be combined with other gRPC compression encodings"
+    );
+}
+
 #[test_log::test]
 fn test_telemetry_disabled() {
     let serve_args = &[
@@ -1937,6 +1956,840 @@ async fn test_show_system() {
         "iox_schema_table_name_exists,_but_should_error_because_we're_concerned_here_with_system_tables",
         format!("{}", result.unwrap_err())
     );
+
+    // 4. Unknown column passed to --select
+    let err = server
+        .show_system(db_name)
+        .table("influxdb_schema")
+        .with_select("mesurement")
+        .run()
+        .unwrap_err()
+        .to_string();
+    assert_contains!(
+        &err,
+        "system table 'influxdb_schema' has no column 'mesurement': did you mean 'measurement'?"
+    );
+
+    // 5. Parquet output without an output file
+    let err = server
+        .show_system(db_name)
+        .with_format("parquet")
+        .table("influxdb_schema")
+        .run()
+        .unwrap_err()
+        .to_string();
+    assert_contains!(&err, "must specify an output file path with `--output`");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_where() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a,t2=b f1=true,f2=4i 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    // A single filter
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table("influxdb_schema")
+        .with_where("key = 't1'")
+        .run()
+        .unwrap();
+    assert_contains!(&output, r#""key":"t1""#);
+    assert_not_contains!(&output, r#""key":"t2""#);
+    assert_not_contains!(&output, r#""key":"f1""#);
+
+    // Multiple filters are combined with AND
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table("influxdb_schema")
+        .with_where("key LIKE 'f%'")
+        .with_where("key != 'f1'")
+        .run()
+        .unwrap();
+    assert_contains!(&output, r#""key":"f2""#);
+    assert_not_contains!(&output, r#""key":"f1""#);
+    assert_not_contains!(&output, r#""key":"t1""#);
+
+    // Counting respects the filters, but not the limit
+    let output = server
+        .show_system(db_name)
+        .table("influxdb_schema")
+        .with_where("key LIKE 'f%'")
+        .with_limit(1)
+        .with_count_only()
+        .run()
+        .unwrap();
+    assert_eq!("2", output.trim());
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table("influxdb_schema")
+        .with_where("key LIKE 'f%'")
+        .with_count_only()
+        .run()
+        .unwrap();
+    assert_eq!(
+        json!({"count": 2}),
+        serde_json::from_str::<Value>(&output).unwrap()
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_timing() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    // the timing is printed to stderr, which the query builders do not capture
+    let show_table = |args: &[&str]| {
+        let output = cargo_bin_cmd!("influxdb3")
+            .args(["show", "system", "--host", &server.client_addr()])
+            .args(["--database", db_name, "table", "--tls-ca"])
+            .args(["../testing-certs/rootCA.pem", "--timing"])
+            .args(args)
+            .arg("databases")
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        (
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    let (stdout, stderr) = show_table(&["--format", "json"]);
+    assert_contains!(&stdout, r#""database_name":"foo""#);
+    assert_contains!(&stderr, "query took");
+    assert_not_contains!(&stdout, "query took");
+
+    let (stdout, stderr) = show_table(&["--count-only", "--where", "database_name = 'foo'"]);
+    assert_eq!("1", stdout.trim());
+    assert_contains!(&stderr, "query took");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_explain() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    let output = server
+        .show_system(db_name)
+        .table("influxdb_schema")
+        .with_where("key LIKE 'f%'")
+        .with_limit(1)
+        .with_explain()
+        .run()
+        .unwrap();
+    assert_contains!(output, "logical_plan");
+    assert_contains!(output, "physical_plan");
+    assert_contains!(output, "fetch=1");
+
+    let output = server
+        .show_system(db_name)
+        .table("influxdb_schema")
+        .with_explain_analyze()
+        .run()
+        .unwrap();
+    assert_contains!(output, "Plan with Metrics");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_after() {
+    let server = TestServer::configure().spawn().await;
+    for db_name in ["a", "b", "c"] {
+        server.create_database(db_name).run().unwrap();
+    }
+
+    let page = |order_by: &str, after: &str, after_key: Option<&str>| {
+        let mut query = server
+            .show_system("a")
+            .with_format("json")
+            .table("databases")
+            .with_select("database_name")
+            .with_where("database_name != '_internal'")
+            .with_order_by(order_by)
+            .with_limit(1)
+            .with_after(after);
+        if let Some(after_key) = after_key {
+            query = query.with_after_key(after_key);
+        }
+        serde_json::from_str::<Value>(&query.run().unwrap()).unwrap()
+    };
+    assert_eq!(
+        json!([{"database_name": "b"}]),
+        page("database_name", "a", None)
+    );
+    assert_eq!(
+        json!([{"database_name": "b"}]),
+        page("database_name DESC", "c", None)
+    );
+
+    // every database has the same value of `deleted`, so they are paged through by name
+    assert_eq!(
+        json!([{"database_name": "b"}]),
+        page("deleted", "false", Some("a"))
+    );
+    assert_eq!(
+        json!([{"database_name": "c"}]),
+        page("deleted", "false", Some("b"))
+    );
+
+    // the next page is printed to stderr, which the query builders do not capture, and is taken
+    // from the page itself, without its keyset columns being added to the output
+    let next_page = |format: &str, order_by: &str, after: &[&str]| {
+        let output = cargo_bin_cmd!("influxdb3")
+            .args(["show", "system", "--host", &server.client_addr()])
+            .args(["--database", "a", "table", "--tls-ca"])
+            .args(["../testing-certs/rootCA.pem", "--format", format])
+            .args(["--select", "database_name", "--where"])
+            .args(["database_name != '_internal'", "--order-by", order_by])
+            .args(["--limit", "2"])
+            .args(after)
+            .arg("databases")
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        (
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+    let (stdout, stderr) = next_page("json", "database_name", &["--after", "_"]);
+    assert_eq!(
+        json!([{"database_name": "a"}, {"database_name": "b"}]),
+        serde_json::from_str::<Value>(&stdout).unwrap()
+    );
+    assert_contains!(&stderr, "next page: --after b\n");
+    let (stdout, stderr) = next_page("csv", "deleted", &["--after", "false", "--after-key", "_"]);
+    assert_eq!("database_name\na\nb", stdout.trim());
+    assert_contains!(&stderr, "next page: --after false --after-key b\n");
+
+    // the default ordering of influxdb_schema is on two columns, so there is nothing to page by
+    let err = server
+        .show_system("a")
+        .table("influxdb_schema")
+        .with_after("f1")
+        .run()
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "--after requires the entries to be ordered by a single column"
+    );
+
+    // and none of its columns identify an entry, so entries at the end of a page could be skipped
+    let err = server
+        .show_system("a")
+        .table("influxdb_schema")
+        .with_order_by("key")
+        .with_after("f1")
+        .run()
+        .unwrap_err();
+    assert_contains!(err.to_string(), "has no column that identifies each entry");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_group_by_interval() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    // the queries made by the CLI are filtered out of the queries table, so check the plan of
+    // the grouped query, rather than its output
+    let output = server
+        .show_system(db_name)
+        .table("queries")
+        .with_select("end2end_duration")
+        .with_group_by_interval("1m")
+        .with_agg("max")
+        .with_explain()
+        .run()
+        .unwrap();
+    assert_contains!(output, "date_bin");
+    assert_contains!(output, "max_end2end_duration");
+
+    let err = server
+        .show_system(db_name)
+        .table("queries")
+        .with_group_by_interval("1h")
+        .with_agg("avg")
+        .run()
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "--agg avg requires the columns to aggregate to be given with --select"
+    );
+
+    let err = server
+        .show_system(db_name)
+        .table("influxdb_schema")
+        .with_group_by_interval("1h")
+        .run()
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "system table 'influxdb_schema' does not have a known time column, so \
+        --group-by-interval cannot be used with it"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_output_file() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    let output_file = NamedTempFile::new().unwrap();
+    let output_path = output_file.path().to_str().unwrap();
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table("influxdb_schema")
+        .with_output(output_path)
+        .run()
+        .unwrap();
+    assert!(output.is_empty(), "nothing should be written to stdout");
+
+    let contents = fs::read_to_string(output_path).unwrap();
+    assert_contains!(&contents, r#""key":"t1""#);
+    assert_contains!(&contents, r#""key":"f1""#);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_summary_multiple_databases() {
+    let server = TestServer::configure().spawn().await;
+
+    for db_name in ["foo", "bar"] {
+        server
+            .write_lp_to_db(
+                db_name,
+                format!("{db_name},t1=a f1=true 1000"),
+                influxdb3_client::Precision::Second,
+            )
+            .await
+            .expect("write to db");
+    }
+
+    let output = server.show_system("foo,bar").summary().run().unwrap();
+    let foo = output.find("database foo:").expect("foo summary");
+    let bar = output.find("database bar:").expect("bar summary");
+    assert!(
+        foo < bar,
+        "databases should be summarized in order: {output}"
+    );
+    assert_contains!(&output[foo..bar], "| foo         | f1");
+    assert_contains!(&output[bar..], "| bar         | f1");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_summary_watch() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    // the summary is re-run until the command is interrupted, here by the timeout
+    let output = cargo_bin_cmd!("influxdb3")
+        .args(["show", "system", "--host", &server.client_addr()])
+        .args(["--database", db_name, "summary", "--tls-ca"])
+        .args(["../testing-certs/rootCA.pem", "--only", "influxdb_schema"])
+        .args(["--watch", "1"])
+        .timeout(Duration::from_millis(3500))
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    let summaries = output.matches("influxdb_schema summary (").count();
+    assert!(summaries >= 2, "expected repeated summaries: {output}");
+    // stdout is not a terminal, so the screen is not cleared, and the summaries are separated
+    assert_contains!(&output, "\n---\n");
+    assert_not_contains!(&output, "\x1B[2J");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_summary_concurrency() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    // the tables are summarized in the same order however many are queried at once
+    let tables = |concurrency: u16| {
+        let output = server
+            .show_system(db_name)
+            .summary()
+            .with_concurrency(concurrency)
+            .run()
+            .unwrap();
+        output
+            .lines()
+            .filter(|line| line.contains(" summary ("))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    let sequential = tables(1);
+    assert!(sequential.len() > 1, "{sequential:?}");
+    assert_eq!(sequential, tables(16));
+
+    let err = server
+        .show_system(db_name)
+        .summary()
+        .with_concurrency(0)
+        .run()
+        .unwrap_err();
+    assert_contains!(err.to_string(), "invalid value '0' for '--concurrency");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_parquet_without_output() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    // only the commands that write to a file can output parquet
+    let err = server
+        .show_system(db_name)
+        .with_format("parquet")
+        .summary()
+        .run()
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "the `parquet` format can only be used with `--output`"
+    );
+    let err = server
+        .show_system(db_name)
+        .with_format("parquet")
+        .table_list()
+        .run()
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "the `parquet` format can only be used with `--output`"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_summary_output_dir() {
+    let server = TestServer::configure().spawn().await;
+
+    for db_name in ["foo", "bar"] {
+        server
+            .write_lp_to_db(
+                db_name,
+                format!("{db_name},t1=a f1=true 1000"),
+                influxdb3_client::Precision::Second,
+            )
+            .await
+            .expect("write to db");
+    }
+
+    let dir = TempDir::new().unwrap();
+    let dir_path = dir.path().to_str().unwrap();
+    let output = server
+        .show_system("foo")
+        .with_format("csv")
+        .summary()
+        .with_output_dir(dir_path)
+        .run()
+        .unwrap();
+    let schema_path = dir.path().join("influxdb_schema.csv");
+    assert_contains!(output, schema_path.to_str().unwrap());
+    let schema = fs::read_to_string(&schema_path).unwrap();
+    assert_eq!(
+        4,
+        schema.lines().count(),
+        "expected a header and t1, f1, and time rows: {schema}"
+    );
+    assert_contains!(schema, "foo,f1");
+
+    // each database is written to its own directory, and parquet can be used
+    server
+        .show_system("foo,bar")
+        .with_format("parquet")
+        .summary()
+        .with_output_dir(dir_path)
+        .run()
+        .unwrap();
+    for db_name in ["foo", "bar"] {
+        let schema = fs::read(dir.path().join(db_name).join("influxdb_schema.parquet")).unwrap();
+        assert!(schema.starts_with(b"PAR1"), "expected a parquet file");
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_list_types() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    let output = server
+        .show_system(db_name)
+        .table_list()
+        .with_types()
+        .run()
+        .unwrap();
+    assert_contains!(output, "size_bytes: UInt64");
+
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table_list()
+        .run()
+        .unwrap();
+    let tables: Value = serde_json::from_str(&output).unwrap();
+    let parquet_files = tables
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["table"] == "parquet_files")
+        .expect("parquet_files system table should be listed");
+    assert!(
+        parquet_files["columns"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({"name": "size_bytes", "type": "UInt64"})),
+        "expected typed size_bytes column in: {parquet_files}"
+    );
+
+    // jsonl has one table per line, in the same shape as json
+    let output = server
+        .show_system(db_name)
+        .with_format("jsonl")
+        .table_list()
+        .run()
+        .unwrap();
+    let parquet_files = output
+        .lines()
+        .map(|l| serde_json::from_str::<Value>(l).unwrap())
+        .find(|t| t["table"] == "parquet_files")
+        .expect("parquet_files system table should be listed");
+    assert!(
+        parquet_files["columns"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({"name": "size_bytes", "type": "UInt64"})),
+        "expected typed size_bytes column in: {parquet_files}"
+    );
+
+    let output = server
+        .show_system(db_name)
+        .with_format("csv")
+        .table_list()
+        .run()
+        .unwrap();
+    let mut lines = output.lines();
+    assert_eq!(Some("table,column,type"), lines.next());
+    assert!(
+        lines.any(|l| l == "parquet_files,size_bytes,UInt64"),
+        "expected size_bytes column in: {output}"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_list_names_only() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table_list()
+        .with_names_only()
+        .run()
+        .unwrap();
+    let tables = serde_json::from_str::<Vec<Value>>(&output).unwrap();
+    assert!(
+        tables.contains(&json!({"table_name": "parquet_files"})),
+        "expected parquet_files in: {output}"
+    );
+    assert!(
+        tables.iter().all(|t| t.as_object().unwrap().len() == 1),
+        "expected only table names in: {output}"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_list_grep() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    let output = server
+        .show_system(db_name)
+        .with_format("json")
+        .table_list()
+        .with_grep("DURATION")
+        .run()
+        .unwrap();
+    let tables = serde_json::from_str::<Vec<Value>>(&output).unwrap();
+    let columns = |table: &str| {
+        tables
+            .iter()
+            .find(|t| t["table"] == table)
+            .unwrap_or_else(|| panic!("expected {table} in: {output}"))["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect::<Vec<_>>()
+    };
+    let queries = columns("queries");
+    assert!(
+        !queries.is_empty(),
+        "expected duration columns in: {output}"
+    );
+    assert!(
+        queries.iter().all(|c| c.contains("duration")),
+        "expected only duration columns in: {output}"
+    );
+    assert!(
+        columns("parquet_files").is_empty(),
+        "expected no parquet_files columns in: {output}"
+    );
+
+    // data types that contain commas are quoted in csv output, with or without a filter
+    let parse_csv = |output: &str| {
+        csv::Reader::from_reader(output.as_bytes())
+            .records()
+            .map(|record| {
+                let record = record.unwrap();
+                assert_eq!(3, record.len(), "expected three fields in: {record:?}");
+                record.iter().map(ToString::to_string).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    let output = server
+        .show_system(db_name)
+        .with_format("csv")
+        .table_list()
+        .with_grep("issue_time")
+        .run()
+        .unwrap();
+    let rows = parse_csv(&output);
+    let issue_time = rows
+        .iter()
+        .find(|row| row[0] == "queries")
+        .unwrap_or_else(|| panic!("expected queries in: {output}"));
+    assert_eq!("issue_time", issue_time[1]);
+    assert!(
+        issue_time[2].starts_with("Timestamp("),
+        "expected a timestamp type in: {output}"
+    );
+    let output = server
+        .show_system(db_name)
+        .with_format("csv")
+        .table_list()
+        .run()
+        .unwrap();
+    assert!(
+        parse_csv(&output).contains(issue_time),
+        "expected the same issue_time row in: {output}"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_describe() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    let output = server
+        .show_system(db_name)
+        .describe("parquet_files")
+        .run()
+        .unwrap();
+    let described: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(described["table_name"], "parquet_files");
+    let column_names = described["columns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["column_name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        column_names,
+        [
+            "table_name",
+            "path",
+            "size_bytes",
+            "row_count",
+            "min_time",
+            "max_time"
+        ]
+    );
+
+    let result = server.show_system(db_name).describe("meow").run();
+    assert_contains!(
+        result.unwrap_err().to_string(),
+        "system table 'meow' not found"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_show_system_table_jsonl() {
+    let server = TestServer::configure().spawn().await;
+    let db_name = "foo";
+
+    server
+        .write_lp_to_db(
+            db_name,
+            "cpu,t1=a f1=true 1000",
+            influxdb3_client::Precision::Second,
+        )
+        .await
+        .expect("write to db");
+
+    let output = server
+        .show_system(db_name)
+        .with_format("jsonl")
+        .table("influxdb_schema")
+        .run()
+        .unwrap();
+    let rows = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("each line is a JSON object"))
+        .collect::<Vec<_>>();
+    assert_eq!(3, rows.len(), "expected t1, f1, and time rows: {output}");
+    assert!(rows.iter().all(|r| r["measurement"] == "cpu"));
+}
+
+#[tokio::test]
+async fn test_show_system_check() {
+    let server = TestServer::configure().with_auth().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    let client_addr = server.client_addr();
+    let show_system = |token: &str| {
+        api::run_cmd_with_result(
+            &[
+                "--database",
+                db_name,
+                "table-list",
+                "--tls-ca",
+                "../testing-certs/rootCA.pem",
+                "--check",
+            ],
+            None,
+            vec!["show", "system", "--host", &client_addr, "--token", token],
+        )
+    };
+
+    let output = show_system(server.token().unwrap()).unwrap();
+    assert_contains!(output, "queries");
+
+    let err = show_system("not-a-valid-token").unwrap_err().to_string();
+    assert_contains!(err, "failed the connectivity check");
+    assert_contains!(err, "401");
+}
+
+#[tokio::test]
+async fn test_show_system_token_file() {
+    let server = TestServer::configure().with_auth().spawn().await;
+    let db_name = "foo";
+    server.create_database(db_name).run().unwrap();
+
+    let mut token_file = NamedTempFile::new().unwrap();
+    writeln!(token_file, "{}", server.token().unwrap()).unwrap();
+    let token_file_path = token_file.path().to_str().unwrap();
+
+    let client_addr = server.client_addr();
+    let show_system = |extra_args: &[&str]| {
+        let mut args = vec!["show", "system", "--host", &client_addr];
+        args.extend_from_slice(extra_args);
+        api::run_cmd_with_result(
+            &[
+                "--database",
+                db_name,
+                "table-list",
+                "--tls-ca",
+                "../testing-certs/rootCA.pem",
+                "--check",
+            ],
+            None,
+            args,
+        )
+    };
+
+    // the trailing newline in the file is ignored
+    let output = show_system(&["--token-file", token_file_path]).unwrap();
+    assert_contains!(output, "queries");
+
+    // --token takes precedence over --token-file
+    let err = show_system(&[
+        "--token",
+        "not-a-valid-token",
+        "--token-file",
+        token_file_path,
+    ])
+    .unwrap_err()
+    .to_string();
+    assert_contains!(err, "401");
+
+    let err = show_system(&["--token-file", "does-not-exist"])
+        .unwrap_err()
+        .to_string();
+    assert_contains!(err, "failed to read token file 'does-not-exist'");
 }
 
 #[tokio::test]
@@ -1,10 +1,20 @@
+use arrow_flight::FlightClient;
 use arrow_flight::Ticket;
+use arrow_flight::flight_service_client::FlightServiceClient;
 use arrow_flight::sql::SqlInfo;
 use arrow_util::assert_batches_eq;
 use arrow_util::assert_batches_sorted_eq;
 use influxdb3_client::Precision;
 use serde_json::json;
 use test_helpers::assert_contains;
+use tonic::codec::CompressionEncoding;
+use tonic_health::pb::{
+    HealthCheckRequest, health_check_response::ServingStatus, health_client::HealthClient,
+};
+use tonic_reflection::pb::v1::{
+    ServerReflectionRequest, server_reflection_client::ServerReflectionClient,
+    server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+};
 
 use crate::server::TestServer;
 use crate::server::collect_stream;
@@ -422,3 +432,134 @@ async fn test_flight_distinct_cache() {
         );
     }
 }
+
+#[test_log::test(tokio::test)]
+async fn flight_compressed_response() {
+    let ticket = || {
+        Ticket::new(
+            r#"{
+                    "database": "foo",
+                    "sql_query": "SELECT host, usage FROM cpu",
+                    "query_type": "sql"
+                }"#,
+        )
+    };
+    let expected = [
+        "+------+-------+",
+        "| host | usage |",
+        "+------+-------+",
+        "| s1   | 0.9   |",
+        "+------+-------+",
+    ];
+
+    let server = TestServer::spawn().await;
+    server
+        .write_lp_to_db(
+            "foo",
+            "cpu,host=s1,region=us-east usage=0.9 2998574936",
+            Precision::Second,
+        )
+        .await
+        .unwrap();
+
+    for (encoding, name) in [
+        (CompressionEncoding::Gzip, "gzip"),
+        (CompressionEncoding::Zstd, "zstd"),
+    ] {
+        // the response is compressed with the encoding the client accepts
+        let mut inner = FlightServiceClient::new(server.grpc_channel().await)
+            .accept_compressed(encoding)
+            .send_compressed(encoding);
+        let response = inner.do_get(ticket()).await.unwrap();
+        assert_eq!(
+            Some(name),
+            response
+                .metadata()
+                .get("grpc-encoding")
+                .map(|e| e.to_str().unwrap()),
+        );
+
+        let mut client = FlightClient::new_from_inner(inner);
+        let response = client.do_get(ticket()).await.unwrap();
+        let batches = collect_stream(response).await;
+        assert_batches_sorted_eq!(expected, &batches);
+    }
+
+    // responses are not compressed when compression is turned off, even if the client accepts it
+    let server = TestServer::configure()
+        .with_grpc_compression("none")
+        .spawn()
+        .await;
+    server
+        .write_lp_to_db(
+            "foo",
+            "cpu,host=s1,region=us-east usage=0.9 2998574936",
+            Precision::Second,
+        )
+        .await
+        .unwrap();
+    let mut inner = FlightServiceClient::new(server.grpc_channel().await)
+        .accept_compressed(CompressionEncoding::Gzip);
+    let response = inner.do_get(ticket()).await.unwrap();
+    assert!(response.metadata().get("grpc-encoding").is_none());
+    let batches = collect_stream(
+        FlightClient::new_from_inner(inner)
+            .do_get(ticket())
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_batches_sorted_eq!(expected, &batches);
+}
+
+#[test_log::test(tokio::test)]
+async fn grpc_health() {
+    let server = TestServer::spawn().await;
+    let mut client = HealthClient::new(server.grpc_channel().await);
+
+    for service in ["", "arrow.flight.protocol.FlightService"] {
+        let response = client
+            .check(HealthCheckRequest {
+                service: service.to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            ServingStatus::Serving,
+            response.status(),
+            "service: {service:?}"
+        );
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn grpc_reflection_lists_served_services() {
+    let server = TestServer::configure().with_grpc_reflection().spawn().await;
+    let mut client = ServerReflectionClient::new(server.grpc_channel().await);
+
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let mut responses = client
+        .server_reflection_info(futures::stream::iter([request]))
+        .await
+        .unwrap()
+        .into_inner();
+    let response = responses.message().await.unwrap().unwrap();
+    let Some(MessageResponse::ListServicesResponse(list)) = response.message_response else {
+        panic!("expected a list of services, got: {response:?}");
+    };
+
+    let mut services = list.service.into_iter().map(|s| s.name).collect::<Vec<_>>();
+    services.sort();
+    assert_eq!(
+        vec![
+            "arrow.flight.protocol.FlightService",
+            "grpc.health.v1.Health",
+            "grpc.reflection.v1.ServerReflection",
+        ],
+        services
+    );
+}
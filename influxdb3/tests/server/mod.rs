@@ -100,6 +100,8 @@ pub struct TestConfig {
     permission_tokens_file: Option<String>,
     object_store_tls_allow_insecure: bool,
     object_store_tls_ca_path: Option<String>,
+    grpc_reflection: bool,
+    grpc_compression: Option<String>,
 }
 
 impl TestConfig {
@@ -211,6 +213,18 @@ impl TestConfig {
         self.object_store_tls_ca_path = Some(path.into());
         self
     }
+
+    /// Register the gRPC server reflection service on this [`TestServer`]
+    pub fn with_grpc_reflection(mut self) -> Self {
+        self.grpc_reflection = true;
+        self
+    }
+
+    /// Set the encodings that Flight responses may be compressed with on this [`TestServer`]
+    pub fn with_grpc_compression<S: Into<String>>(mut self, encodings: S) -> Self {
+        self.grpc_compression = Some(encodings.into());
+        self
+    }
 }
 
 impl ConfigProvider for TestConfig {
@@ -294,6 +308,17 @@ impl ConfigProvider for TestConfig {
             ])
         }
 
+        if self.grpc_reflection {
+            args.push("--grpc-reflection".to_string());
+        }
+
+        if let Some(encodings) = &self.grpc_compression {
+            args.append(&mut vec![
+                "--grpc-compression".to_string(),
+                encodings.to_owned(),
+            ]);
+        }
+
         args
     }
 
@@ -599,34 +624,29 @@ impl TestServer {
 
     /// Get a [`FlightSqlClient`] for making requests to the running service over gRPC
     pub async fn flight_sql_client(&self, database: &str) -> FlightSqlClient {
-        let cert = tonic::transport::Certificate::from_pem(
-            std::fs::read("../testing-certs/rootCA.pem").unwrap(),
-        );
-        let channel = tonic::transport::Channel::from_shared(self.client_addr())
-            .expect("create tonic channel")
-            .tls_config(ClientTlsConfig::new().ca_certificate(cert))
-            .unwrap()
-            .connect()
-            .await
-            .expect("connect to gRPC client");
-        let mut client = FlightSqlClient::new(channel);
+        let mut client = FlightSqlClient::new(self.grpc_channel().await);
         client.add_header("database", database).unwrap();
         client
     }
 
     /// Get a raw [`FlightClient`] for performing Flight actions directly
     pub async fn flight_client(&self) -> FlightClient {
+        FlightClient::new(self.grpc_channel().await)
+    }
+
+    /// Get a [`tonic::transport::Channel`] connected to the running service, for use with any
+    /// gRPC client
+    pub async fn grpc_channel(&self) -> tonic::transport::Channel {
         let cert = tonic::transport::Certificate::from_pem(
             std::fs::read("../testing-certs/rootCA.pem").unwrap(),
         );
-        let channel = tonic::transport::Channel::from_shared(self.client_addr())
+        tonic::transport::Channel::from_shared(self.client_addr())
             .expect("create tonic channel")
             .tls_config(ClientTlsConfig::new().ca_certificate(cert))
             .unwrap()
             .connect()
             .await
-            .expect("connect to gRPC client");
-        FlightClient::new(channel)
+            .expect("connect to gRPC client")
     }
 
     pub fn http_client(&self) -> &reqwest::Client {
@@ -0,0 +1,333 @@
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use authz::{Authorization, Authorizer as IoxAuthorizer, Error as IoxError, Permission};
+use hashbrown::HashMap;
+use iox_time::{Time, TimeProvider};
+use observability_deps::tracing::trace;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha512};
+
+/// An [`IoxAuthorizer`] that caches successful authorizations from an inner authorizer
+///
+/// Entries are keyed on a hash of the token, and each holds the authorizations made for up to
+/// [`MAX_GRANTS_PER_TOKEN`] different sets of permissions, so a token that is checked for several
+/// sets of permissions, e.g., for queries to different databases, does not evict its own
+/// authorizations in turn. A cached authorization is only used for a request that asks for
+/// the same permissions, and expires `ttl` after it was inserted, so a token that is revoked in
+/// the inner authorizer stops being accepted within `ttl`. Failed authorizations are never cached.
+/// When the cache is full, the least recently used token is evicted.
+#[derive(Debug)]
+pub struct CachingAuthorizer {
+    inner: Arc<dyn IoxAuthorizer>,
+    time_provider: Arc<dyn TimeProvider>,
+    ttl: Duration,
+    capacity: NonZeroUsize,
+    cache: Mutex<Cache>,
+}
+
+impl CachingAuthorizer {
+    pub fn new(
+        inner: Arc<dyn IoxAuthorizer>,
+        time_provider: Arc<dyn TimeProvider>,
+        ttl: Duration,
+        capacity: NonZeroUsize,
+    ) -> Self {
+        Self {
+            inner,
+            time_provider,
+            ttl,
+            capacity,
+            cache: Default::default(),
+        }
+    }
+}
+
+/// The maximum number of sets of permissions cached for each token, once reached, the oldest
+/// authorization for the token is replaced
+const MAX_GRANTS_PER_TOKEN: usize = 16;
+
+#[async_trait]
+impl IoxAuthorizer for CachingAuthorizer {
+    async fn authorize(
+        &self,
+        token: Option<Vec<u8>>,
+        perms: &[Permission],
+    ) -> Result<Authorization, IoxError> {
+        // requests without a token are cheap to reject, so there is nothing to cache
+        let Some(token) = token else {
+            return self.inner.authorize(None, perms).await;
+        };
+        let key = Sha512::digest(&token).to_vec();
+
+        let now = self.time_provider.now();
+        if let Some(authorization) = self.cache.lock().get(&key, perms, now) {
+            trace!("authorization cache hit");
+            return Ok(authorization);
+        }
+
+        let authorization = self.inner.authorize(Some(token), perms).await?;
+        self.cache.lock().insert(
+            key,
+            Grant {
+                perms: perms.to_vec(),
+                authorization: authorization.clone(),
+                expires_at: now + self.ttl,
+            },
+            now,
+            self.capacity,
+        );
+        Ok(authorization)
+    }
+
+    async fn probe(&self) -> Result<(), IoxError> {
+        self.inner.probe().await
+    }
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    entries: HashMap<Vec<u8>, CacheEntry>,
+    /// Incremented on every access, used to find the least recently used entry
+    clock: u64,
+}
+
+/// The cached authorizations for a single token
+#[derive(Debug, Default)]
+struct CacheEntry {
+    /// In the order they were inserted
+    grants: Vec<Grant>,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn is_live(&self, now: Time) -> bool {
+        self.grants.iter().any(|g| g.expires_at > now)
+    }
+}
+
+/// An authorization made for a token with a particular set of permissions
+#[derive(Debug)]
+struct Grant {
+    perms: Vec<Permission>,
+    authorization: Authorization,
+    expires_at: Time,
+}
+
+impl Cache {
+    fn get(&mut self, key: &[u8], perms: &[Permission], now: Time) -> Option<Authorization> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.grants.retain(|g| g.expires_at > now);
+        if entry.grants.is_empty() {
+            self.entries.remove(key);
+            return None;
+        }
+        let grant = entry.grants.iter().find(|g| g.perms == perms)?;
+        entry.last_used = self.clock;
+        Some(grant.authorization.clone())
+    }
+
+    fn insert(&mut self, key: Vec<u8>, grant: Grant, now: Time, capacity: NonZeroUsize) {
+        self.clock += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= capacity.get() {
+            self.entries.retain(|_, e| e.is_live(now));
+            if self.entries.len() >= capacity.get()
+                && let Some(lru) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, e)| e.last_used)
+                    .map(|(k, _)| k.clone())
+            {
+                self.entries.remove(&lru);
+            }
+        }
+        let entry = self.entries.entry(key).or_default();
+        entry.last_used = self.clock;
+        entry
+            .grants
+            .retain(|g| g.expires_at > now && g.perms != grant.perms);
+        if entry.grants.len() >= MAX_GRANTS_PER_TOKEN {
+            entry.grants.remove(0);
+        }
+        entry.grants.push(grant);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use authz::{
+        Action, Authorization, Authorizer as IoxAuthorizer, Error as IoxError, Permission,
+        Resource, Target,
+    };
+    use iox_time::{MockProvider, Time};
+    use parking_lot::Mutex;
+
+    use super::CachingAuthorizer;
+
+    /// Authorizes any token in `valid`, counting the calls made to it
+    #[derive(Debug, Default)]
+    struct CountingAuthorizer {
+        valid: Mutex<Vec<Vec<u8>>>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl IoxAuthorizer for CountingAuthorizer {
+        async fn authorize(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Authorization, IoxError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let token = token.ok_or(IoxError::NoToken)?;
+            if self.valid.lock().contains(&token) {
+                Ok(Authorization::new(None, perms.to_vec()))
+            } else {
+                Err(IoxError::InvalidToken)
+            }
+        }
+    }
+
+    fn setup(
+        capacity: usize,
+    ) -> (
+        Arc<CountingAuthorizer>,
+        Arc<MockProvider>,
+        CachingAuthorizer,
+    ) {
+        let inner = Arc::new(CountingAuthorizer::default());
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let authorizer = CachingAuthorizer::new(
+            Arc::clone(&inner) as _,
+            Arc::clone(&time_provider) as _,
+            Duration::from_secs(10),
+            NonZeroUsize::new(capacity).unwrap(),
+        );
+        (inner, time_provider, authorizer)
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_caches_until_ttl_expires() {
+        let (inner, time_provider, authorizer) = setup(10);
+        inner.valid.lock().push(b"token".to_vec());
+
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap();
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(1, inner.calls.load(Ordering::SeqCst));
+
+        // revoke the token, it is still served from the cache until the entry expires
+        inner.valid.lock().clear();
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(1, inner.calls.load(Ordering::SeqCst));
+
+        time_provider.inc(Duration::from_secs(10));
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap_err();
+        assert_eq!(2, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_does_not_cache_failures() {
+        let (inner, _, authorizer) = setup(10);
+
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap_err();
+        authorizer.authorize(None, &[]).await.unwrap_err();
+        authorizer.authorize(None, &[]).await.unwrap_err();
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+
+        inner.valid.lock().push(b"token".to_vec());
+        authorizer
+            .authorize(Some(b"token".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(4, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_evicts_least_recently_used() {
+        let (inner, _, authorizer) = setup(2);
+        inner
+            .valid
+            .lock()
+            .extend([b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+
+        authorizer
+            .authorize(Some(b"a".to_vec()), &[])
+            .await
+            .unwrap();
+        authorizer
+            .authorize(Some(b"b".to_vec()), &[])
+            .await
+            .unwrap();
+        // use `a` so that `b` is the least recently used when `c` is inserted
+        authorizer
+            .authorize(Some(b"a".to_vec()), &[])
+            .await
+            .unwrap();
+        authorizer
+            .authorize(Some(b"c".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+
+        authorizer
+            .authorize(Some(b"a".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+        authorizer
+            .authorize(Some(b"b".to_vec()), &[])
+            .await
+            .unwrap();
+        assert_eq!(4, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_caches_each_set_of_permissions() {
+        let (inner, _, authorizer) = setup(10);
+        inner.valid.lock().push(b"token".to_vec());
+        let read = [Permission::ResourceAction(
+            Resource::Database(Target::ResourceName("foo".to_string())),
+            Action::Read,
+        )];
+
+        // alternating between two sets of permissions for the same token only calls the inner
+        // authorizer once for each
+        for _ in 0..3 {
+            authorizer
+                .authorize(Some(b"token".to_vec()), &[])
+                .await
+                .unwrap();
+            authorizer
+                .authorize(Some(b"token".to_vec()), &read)
+                .await
+                .unwrap();
+        }
+        assert_eq!(2, inner.calls.load(Ordering::SeqCst));
+    }
+}
@@ -11,6 +11,9 @@ use serde::Serialize;
 use sha2::{Digest, Sha512};
 use std::fmt::Debug;
 
+mod caching;
+pub use caching::CachingAuthorizer;
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub struct DatabaseActions(u16);
 
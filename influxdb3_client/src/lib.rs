@@ -1,15 +1,31 @@
 use bytes::Bytes;
+use flate2::{
+    Compression,
+    read::GzDecoder,
+    write::{self, GzEncoder},
+};
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
 use hashbrown::HashMap;
 use influxdb3_catalog::log::{OrderedCatalogBatch, TriggerSettings};
 use iox_query_params::StatementParam;
 use reqwest::{
-    Body, Certificate, IntoUrl, Method, StatusCode,
-    header::{CONTENT_TYPE, HeaderMap, HeaderValue},
+    Body, Certificate, Identity, IntoUrl, Method, StatusCode,
+    header::{
+        ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue,
+        RETRY_AFTER,
+    },
     tls::Version,
 };
 use secrecy::{ExposeSecret, Secret};
 use serde::{Serialize, de::DeserializeOwned};
-use std::{fmt::Display, num::NonZeroUsize, path::PathBuf, string::FromUtf8Error, time::Duration};
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    num::NonZeroUsize,
+    path::PathBuf,
+    string::FromUtf8Error,
+    time::Duration,
+};
 use url::Url;
 
 use influxdb3_types::http::*;
@@ -27,6 +43,12 @@ pub enum Error {
     #[error("failed to read the API response bytes: {0}")]
     Bytes(#[source] reqwest::Error),
 
+    #[error("failed to gzip the request body: {0}")]
+    Compress(#[source] std::io::Error),
+
+    #[error("failed to decompress the gzipped API response: {0}")]
+    Decompress(#[source] std::io::Error),
+
     #[error("failed to serialize the request body: {0}")]
     RequestSerialization(#[source] serde_json::Error),
 
@@ -60,6 +82,14 @@ pub enum Error {
         source: reqwest::Error,
     },
 
+    #[error("{method} {url} request timed out: {source}")]
+    Timeout {
+        method: Method,
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
     #[error("failed to build an http client: {0}")]
     Builder(#[source] reqwest::Error),
 
@@ -69,10 +99,18 @@ pub enum Error {
 
 impl Error {
     fn request_send(method: Method, url: impl Into<String>, source: reqwest::Error) -> Self {
-        Self::RequestSend {
-            method,
-            url: url.into(),
-            source,
+        if source.is_timeout() {
+            Self::Timeout {
+                method,
+                url: url.into(),
+                source,
+            }
+        } else {
+            Self::RequestSend {
+                method,
+                url: url.into(),
+                source,
+            }
         }
     }
 }
@@ -88,36 +126,90 @@ pub struct Client {
     base_url: Url,
     /// The `Bearer` token to use for authenticating on each request to the server
     auth_token: Option<Secret<String>>,
+    /// The timeout applied to each request, from when it is sent until the response body has
+    /// been read, except for streamed responses
+    timeout: Option<Duration>,
+    /// Whether to request gzip compressed responses and compress write request bodies
+    compression: bool,
+    /// Additional headers that will be sent with each request to the server
+    headers: HeaderMap,
+    /// The options used to build `http_client`, kept so that it can be rebuilt when they change
+    http_config: HttpClientConfig,
     /// A [`reqwest::Client`] for handling HTTP requests
+    ///
+    /// This holds the connection pool, which is shared by every request made with this client,
+    /// and any of its clones, so that connections are reused rather than re-established.
     http_client: reqwest::Client,
 }
 
-impl Client {
-    /// Create a new [`Client`]
-    pub fn new<U: IntoUrl>(base_url: U, ca_cert: Option<PathBuf>) -> Result<Self> {
-        let client = reqwest::Client::builder()
+/// Options for building the [`reqwest::Client`] used by a [`Client`]
+#[derive(Debug, Clone, Default)]
+struct HttpClientConfig {
+    ca_cert: Option<Certificate>,
+    identity: Option<Identity>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl HttpClientConfig {
+    fn build(&self) -> Result<reqwest::Client> {
+        let mut client = reqwest::Client::builder()
             .min_tls_version(Version::TLS_1_3)
             .use_rustls_tls();
+        if let Some(cert) = &self.ca_cert {
+            client = client.add_root_certificate(cert.clone());
+        }
+        if let Some(identity) = &self.identity {
+            client = client.identity(identity.clone());
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            client = client.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            client = client.tcp_keepalive(interval);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            client = client.read_timeout(timeout);
+        }
+        client.build().map_err(Error::Builder)
+    }
+}
 
-        let http_client = if let Some(ca_cert) = ca_cert {
-            let cert = std::fs::read(&ca_cert)?;
-            let cert = match ca_cert.extension().and_then(|s| s.to_str()) {
-                Some("der") => Certificate::from_der(&cert),
-                Some("pem") | Some(_) | None => Certificate::from_pem(&cert),
-            }
-            .map_err(Error::Builder)?;
-            client
-                .add_root_certificate(cert)
-                .build()
-                .map_err(Error::Builder)?
-        } else {
-            client.build().map_err(Error::Builder)?
+impl Client {
+    /// Create a new [`Client`]
+    pub fn new<U: IntoUrl>(base_url: U, ca_cert: Option<PathBuf>) -> Result<Self> {
+        let ca_cert = ca_cert
+            .map(|ca_cert| {
+                let cert = std::fs::read(&ca_cert)?;
+                match ca_cert.extension().and_then(|s| s.to_str()) {
+                    Some("der") => Certificate::from_der(&cert),
+                    Some("pem") | Some(_) | None => Certificate::from_pem(&cert),
+                }
+                .map_err(Error::Builder)
+            })
+            .transpose()?;
+        let http_config = HttpClientConfig {
+            ca_cert,
+            ..Default::default()
         };
 
         Ok(Self {
             base_url: base_url.into_url().map_err(Error::BaseUrl)?,
             auth_token: None,
-            http_client,
+            timeout: None,
+            compression: false,
+            headers: HeaderMap::new(),
+            http_client: http_config.build()?,
+            http_config,
         })
     }
 
@@ -140,6 +232,198 @@ impl Client {
         self
     }
 
+    /// Set a timeout for each request made to the server
+    ///
+    /// Requests that do not complete within the timeout fail with [`Error::Timeout`]. By default
+    /// there is no timeout.
+    ///
+    /// Streamed responses, e.g., from [`QueryRequestBuilder::send_stream`], are not subject to
+    /// this timeout, as they may take any amount of time to be read in full. Use
+    /// [`with_read_timeout`][Client::with_read_timeout] to bound how long a stream can stall.
+    ///
+    /// # Example
+    /// ```
+    /// # use influxdb3_client::Client;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("http://localhost:8181", None)?
+    ///     .with_timeout(Duration::from_secs(30));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enable or disable gzip compression
+    ///
+    /// When enabled, responses from the query APIs are requested with `Accept-Encoding: gzip`
+    /// and transparently decompressed, and line protocol bodies sent to the write API are gzip
+    /// compressed. Write bodies that are streamed, rather than provided up front, are always sent
+    /// uncompressed. Compression is disabled by default.
+    ///
+    /// # Example
+    /// ```
+    /// # use influxdb3_client::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("http://localhost:8181", None)?
+    ///     .with_compression(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Add a header that will be sent with each request to the server
+    ///
+    /// Headers are sent alongside the `Authorization` header set by
+    /// [`with_auth_token`][Client::with_auth_token], and setting the same header more than once
+    /// replaces its previous value.
+    ///
+    /// # Example
+    /// ```
+    /// # use influxdb3_client::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// use reqwest::header::{HeaderName, HeaderValue};
+    ///
+    /// let client = Client::new("http://localhost:8181", None)?.with_header(
+    ///     HeaderName::from_static("x-tenant-id"),
+    ///     HeaderValue::from_static("tenant-a"),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Add a set of headers that will be sent with each request to the server
+    ///
+    /// See [`with_header`][Client::with_header] for how these are combined with other headers.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Present a TLS client certificate to the server, for servers, or proxies in front of them,
+    /// that require mutual TLS
+    ///
+    /// Both the certificate chain and the private key are PEM encoded. The key may be in PKCS#8,
+    /// PKCS#1 (RSA), or SEC1 (EC) format.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use influxdb3_client::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let cert = std::fs::read("client.pem")?;
+    /// let key = std::fs::read("client.key")?;
+    /// let client = Client::new("https://localhost:8181", None)?
+    ///     .with_client_identity(&cert, &key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_client_identity(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let mut pem = Vec::with_capacity(cert_pem.len() + key_pem.len() + 1);
+        pem.extend_from_slice(cert_pem);
+        pem.push(b'\n');
+        pem.extend_from_slice(key_pem);
+        self.http_config.identity = Some(Identity::from_pem(&pem).map_err(Error::Builder)?);
+        self.rebuild_http_client()
+    }
+
+    /// Trust the given PEM encoded CA certificate when verifying the server's certificate
+    ///
+    /// This is an alternative to passing the path of a CA certificate to [`Client::new`], and
+    /// replaces any certificate given there.
+    pub fn with_root_ca(mut self, ca_pem: &[u8]) -> Result<Self> {
+        self.http_config.ca_cert = Some(Certificate::from_pem(ca_pem).map_err(Error::Builder)?);
+        self.rebuild_http_client()
+    }
+
+    /// Set the maximum number of idle connections to keep open to the server
+    ///
+    /// Connections are pooled and reused across requests, so a program that makes many requests,
+    /// e.g., one per table, only pays for establishing a connection, and the TLS handshake, once
+    /// per concurrent request. By default there is no limit.
+    ///
+    /// # Example
+    /// ```
+    /// # use influxdb3_client::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("http://localhost:8181", None)?
+    ///     .with_pool_max_idle_per_host(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Result<Self> {
+        self.http_config.pool_max_idle_per_host = Some(max);
+        self.rebuild_http_client()
+    }
+
+    /// Set how long an idle pooled connection is kept open before it is closed
+    ///
+    /// The default is 90 seconds.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.http_config.pool_idle_timeout = Some(timeout);
+        self.rebuild_http_client()
+    }
+
+    /// Set a timeout for establishing each connection to the server
+    ///
+    /// By default there is no connect timeout.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.http_config.connect_timeout = Some(timeout);
+        self.rebuild_http_client()
+    }
+
+    /// Set a timeout for each read from a connection to the server
+    ///
+    /// Unlike [`with_timeout`][Client::with_timeout], this applies to streamed responses, which
+    /// fail with [`Error::Timeout`] if the server sends nothing for this long, however long the
+    /// stream as a whole takes. By default there is no read timeout.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.http_config.read_timeout = Some(timeout);
+        self.rebuild_http_client()
+    }
+
+    /// Send TCP keep-alive probes on the given interval, so that idle pooled connections are not
+    /// dropped by intermediate proxies or load balancers
+    ///
+    /// TCP keep-alive is disabled by default.
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> Result<Self> {
+        self.http_config.tcp_keepalive = Some(interval);
+        self.rebuild_http_client()
+    }
+
+    /// Replace the [`reqwest::Client`] after its options have changed
+    ///
+    /// Connections pooled by the previous client are not carried over, so this should be done
+    /// while the client is being set up, before any requests are made.
+    fn rebuild_http_client(mut self) -> Result<Self> {
+        self.http_client = self.http_config.build()?;
+        Ok(self)
+    }
+
+    /// Create a request builder with the custom headers, authentication token, and timeout
+    /// applied
+    fn request(&self, method: Method, url: Url) -> reqwest::RequestBuilder {
+        let mut req = self
+            .http_client
+            .request(method, url)
+            .headers(self.headers.clone());
+        if let Some(token) = &self.auth_token {
+            req = req.bearer_auth(token.expose_secret());
+        }
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+        req
+    }
+
     /// Compose a request to the `/api/v3/write_lp` API
     ///
     /// # Example
@@ -170,6 +454,7 @@ impl Client {
                 no_sync: None,
             },
             body: NoBody,
+            retry: None,
         }
     }
 
@@ -793,11 +1078,8 @@ impl Client {
     /// status and gather `version` and `revision` information
     pub async fn ping(&self) -> Result<PingResponse> {
         let url = self.base_url.join("/ping")?;
-        let mut req = self.http_client.get(url);
-        if let Some(t) = &self.auth_token {
-            req = req.bearer_auth(t.expose_secret());
-        }
-        let resp = req
+        let resp = self
+            .request(Method::GET, url)
             .send()
             .await
             .map_err(|src| Error::request_send(Method::GET, "/ping", src))?;
@@ -887,8 +1169,74 @@ impl Client {
         url_path: &str,
         body: Option<B>,
         query: Option<Q>,
-        mut headers: Option<HeaderMap>,
+        headers: Option<HeaderMap>,
     ) -> Result<Bytes>
+    where
+        B: Serialize + Send + Sync,
+        Q: Serialize + Send + Sync,
+    {
+        let resp = self
+            .send_json_get_response(method, url_path, body, query, headers)
+            .await?;
+        read_body(resp).await
+    }
+
+    /// Serialize the given `B` to json then send the request and return a stream over the chunks
+    /// of the response body.
+    async fn send_json_get_stream<B, Q>(
+        &self,
+        method: Method,
+        url_path: &str,
+        body: Option<B>,
+        query: Option<Q>,
+        headers: Option<HeaderMap>,
+    ) -> Result<BoxStream<'static, Result<Bytes>>>
+    where
+        B: Serialize + Send + Sync,
+        Q: Serialize + Send + Sync,
+    {
+        // the response may take longer than the request timeout to stream in full, so only the
+        // read timeout, if one is set, applies
+        let client = Self {
+            timeout: None,
+            ..self.clone()
+        };
+        let resp = client
+            .send_json_get_response(method.clone(), url_path, body, query, headers)
+            .await?;
+        let gzipped = is_gzipped(&resp);
+        let url = resp.url().to_string();
+        let stream = resp
+            .bytes_stream()
+            .map_err(move |source| {
+                if source.is_timeout() {
+                    Error::Timeout {
+                        method: method.clone(),
+                        url: url.clone(),
+                        source,
+                    }
+                } else {
+                    Error::Bytes(source)
+                }
+            })
+            .boxed();
+        if gzipped {
+            Ok(gunzip_stream(stream))
+        } else {
+            Ok(stream)
+        }
+    }
+
+    /// Serialize the given `B` to json then send the request, returning the response if it was
+    /// successful.
+    async fn send_json_get_response<B, Q>(
+        &self,
+        method: Method,
+        url_path: &str,
+        body: Option<B>,
+        query: Option<Q>,
+        mut headers: Option<HeaderMap>,
+    ) -> Result<reqwest::Response>
     where
         B: Serialize + Send + Sync,
         Q: Serialize + Send + Sync,
@@ -903,7 +1251,7 @@ impl Client {
             CONTENT_TYPE,
             HeaderValue::from_str("application/json").unwrap(),
         );
-        self.send_get_bytes(method, url_path, b, query, headers)
+        self.send_get_response(method, url_path, b, query, headers)
             .await
     }
 
@@ -917,14 +1265,49 @@ impl Client {
         query: Option<Q>,
         headers: Option<HeaderMap>,
     ) -> Result<Bytes>
+    where
+        Q: Serialize + Send + Sync,
+    {
+        let resp = self
+            .send_get_response(method, url_path, body, query, headers)
+            .await?;
+        read_body(resp).await
+    }
+
+    /// Send an HTTP request with the specified parameters, returning the response if it was
+    /// successful, or an [`Error::ApiError`] containing the response body otherwise.
+    async fn send_get_response<Q>(
+        &self,
+        method: Method,
+        url_path: &str,
+        body: Option<Body>,
+        query: Option<Q>,
+        headers: Option<HeaderMap>,
+    ) -> Result<reqwest::Response>
+    where
+        Q: Serialize + Send + Sync,
+    {
+        let resp = self
+            .send_request(method, url_path, body, query, headers)
+            .await?;
+        error_for_status(resp).await
+    }
+
+    /// Send an HTTP request with the specified parameters, returning the response regardless of
+    /// its status
+    async fn send_request<Q>(
+        &self,
+        method: Method,
+        url_path: &str,
+        body: Option<Body>,
+        query: Option<Q>,
+        headers: Option<HeaderMap>,
+    ) -> Result<reqwest::Response>
     where
         Q: Serialize + Send + Sync,
     {
         let url = self.base_url.join(url_path)?;
-        let mut req = self.http_client.request(method.clone(), url.clone());
-        if let Some(token) = &self.auth_token {
-            req = req.bearer_auth(token.expose_secret());
-        }
+        let mut req = self.request(method.clone(), url.clone());
         if let Some(body) = body {
             req = req.body(body);
         }
@@ -934,20 +1317,12 @@ impl Client {
         if let Some(headers) = headers {
             req = req.headers(headers);
         }
-        let resp = req
-            .send()
-            .await
-            .map_err(|src| Error::request_send(method, url, src))?;
-        let status = resp.status();
-        let content = resp.bytes().await.map_err(Error::Bytes)?;
-
-        match status {
-            s if s.is_success() => Ok(content),
-            code => Err(Error::ApiError {
-                code,
-                message: String::from_utf8(content.to_vec()).map_err(Error::InvalidUtf8)?,
-            }),
+        if self.compression {
+            req = req.header(ACCEPT_ENCODING, "gzip");
         }
+        req.send()
+            .await
+            .map_err(|src| Error::request_send(method, url, src))
     }
 
     /// Send an HTTP request and return `Some(O)` if the response status is HTTP 201 Created.
@@ -964,10 +1339,7 @@ impl Client {
         O: DeserializeOwned + Send + Sync,
     {
         let url = self.base_url.join(url_path)?;
-        let mut req = self.http_client.request(method.clone(), url.clone());
-        if let Some(token) = &self.auth_token {
-            req = req.bearer_auth(token.expose_secret());
-        }
+        let mut req = self.request(method.clone(), url.clone());
         if let Some(body) = body {
             req = req.json(&body);
         }
@@ -1006,10 +1378,7 @@ impl Client {
         O: DeserializeOwned + Send + Sync,
     {
         let url = self.base_url.join(url_path)?;
-        let mut req = self.http_client.request(method.clone(), url.clone());
-        if let Some(token) = &self.auth_token {
-            req = req.bearer_auth(token.expose_secret());
-        }
+        let mut req = self.request(method.clone(), url.clone());
         if let Some(body) = body {
             req = req.json(&body);
         }
@@ -1040,10 +1409,14 @@ pub struct WriteRequestBuilder<'c, B> {
     client: &'c Client,
     params: WriteParams,
     body: B,
+    retry: Option<RetryConfig>,
 }
 
 impl<B> WriteRequestBuilder<'_, B> {
-    /// Set the precision
+    /// Set the precision of the timestamps in the line protocol body
+    ///
+    /// If not set, the `precision` parameter is omitted and the server defaults to
+    /// [`Precision::Auto`], inferring the unit of each timestamp from its magnitude.
     pub fn precision(mut self, set_to: Precision) -> Self {
         self.params.precision = Some(set_to);
         self
@@ -1060,6 +1433,31 @@ impl<B> WriteRequestBuilder<'_, B> {
         self.params.no_sync = Some(set_to);
         self
     }
+
+    /// Retry the write if it fails with a transient error, see [`RetryConfig`]
+    ///
+    /// Writes are not retried by default. Bodies that are streamed, rather than provided up
+    /// front, cannot be replayed and so are never retried.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use influxdb3_client::{Client, RetryConfig};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("http://localhost:8181", None)?;
+    /// client
+    ///     .api_v3_write_lp("db_name")
+    ///     .with_retry(RetryConfig::default())
+    ///     .body("cpu,host=s1 usage=0.5")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
 }
 
 impl<'c> WriteRequestBuilder<'c, NoBody> {
@@ -1072,6 +1470,7 @@ impl<'c> WriteRequestBuilder<'c, NoBody> {
             client: self.client,
             params: self.params,
             body: body.into(),
+            retry: self.retry,
         }
     }
 }
@@ -1079,22 +1478,203 @@ impl<'c> WriteRequestBuilder<'c, NoBody> {
 impl WriteRequestBuilder<'_, Body> {
     /// Send the request to the server
     pub async fn send(self) -> Result<()> {
-        // ignore the returned value since we don't expect a response body
-        let _bytes = self
-            .client
-            .send_get_bytes(
-                Method::POST,
-                "/api/v3/write_lp",
-                Some(self.body),
-                Some(self.params),
-                None,
-            )
-            .await?;
+        let (body, headers) = match self.body.as_bytes() {
+            Some(bytes) if self.client.compression => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(bytes).map_err(Error::Compress)?;
+                let gzipped = encoder.finish().map_err(Error::Compress)?;
+                let mut headers = HeaderMap::new();
+                headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+                (Body::from(gzipped), Some(headers))
+            }
+            _ => (self.body, None),
+        };
+        // a body can only be re-sent if it is held in memory
+        let (retry, replay) = match (self.retry, body.as_bytes()) {
+            (Some(retry), Some(bytes)) => (retry, Some(Bytes::copy_from_slice(bytes))),
+            _ => (RetryConfig::none(), None),
+        };
 
-        Ok(())
+        let mut body = Some(body);
+        let mut attempt = 0;
+        loop {
+            let body = body
+                .take()
+                .or_else(|| replay.clone().map(Body::from))
+                .expect("a body is only retried when it can be replayed");
+            let delay = match self
+                .client
+                .send_request(
+                    Method::POST,
+                    "/api/v3/write_lp",
+                    Some(body),
+                    Some(&self.params),
+                    headers.clone(),
+                )
+                .await
+            {
+                Ok(resp) if attempt < retry.max_retries && is_retryable_status(resp.status()) => {
+                    // never wait longer than the configured maximum, however long the server asks
+                    retry_after(&resp)
+                        .map(|delay| delay.min(retry.max_backoff))
+                        .unwrap_or_else(|| retry.backoff(attempt))
+                }
+                Ok(resp) => {
+                    // ignore the returned value since we don't expect a response body
+                    let _bytes = read_body(error_for_status(resp).await?).await?;
+                    return Ok(());
+                }
+                Err(e) if attempt < retry.max_retries && is_retryable_error(&e) => {
+                    retry.backoff(attempt)
+                }
+                Err(e) => return Err(e),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
     }
 }
 
+/// Options for retrying requests that fail with a transient error
+///
+/// A request is retried if the server could not be reached, the connection was reset, or the
+/// server responded with `429 Too Many Requests` or `503 Service Unavailable`. Any other error,
+/// e.g., a `400 Bad Request` for invalid line protocol, is returned immediately.
+///
+/// The delay before each retry grows exponentially from `initial_backoff`, up to `max_backoff`,
+/// with a random jitter applied so that many clients do not retry in lockstep. If the server
+/// responds with a `Retry-After` header, given in seconds, that delay is used instead, though it is
+/// still capped at `max_backoff`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// The maximum number of times to retry a request
+    pub max_retries: u32,
+    /// The delay before the first retry
+    pub initial_backoff: Duration,
+    /// The maximum delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// A config that never retries
+    fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// The delay before the retry following the given attempt, with jitter applied
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        // use "equal jitter", so that the delay still grows with each attempt
+        let half = backoff / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+}
+
+/// Whether a response with the given status is worth retrying
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    )
+}
+
+/// Whether a request that failed to send is worth retrying, i.e., the server could not be
+/// reached or the connection was reset before a response was received
+fn is_retryable_error(e: &Error) -> bool {
+    match e {
+        Error::RequestSend { source, .. } => source.is_connect() || source.is_request(),
+        _ => false,
+    }
+}
+
+/// The delay requested by the server in the `Retry-After` header of the response, if given in
+/// seconds
+fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    resp.headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Return the response if it was successful, or an [`Error::ApiError`] containing the response
+/// body otherwise
+async fn error_for_status(resp: reqwest::Response) -> Result<reqwest::Response> {
+    match resp.status() {
+        s if s.is_success() => Ok(resp),
+        code => {
+            let content = read_body(resp).await?;
+            Err(Error::ApiError {
+                code,
+                message: String::from_utf8(content.to_vec()).map_err(Error::InvalidUtf8)?,
+            })
+        }
+    }
+}
+
+/// Whether the response body is gzip compressed, according to its `Content-Encoding` header
+fn is_gzipped(resp: &reqwest::Response) -> bool {
+    resp.headers()
+        .get(CONTENT_ENCODING)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"gzip"))
+}
+
+/// Read the entire response body, decompressing it if it is gzip compressed
+async fn read_body(resp: reqwest::Response) -> Result<Bytes> {
+    let gzipped = is_gzipped(&resp);
+    let bytes = resp.bytes().await.map_err(Error::Bytes)?;
+    if gzipped {
+        let mut decoded = Vec::new();
+        GzDecoder::new(bytes.as_ref())
+            .read_to_end(&mut decoded)
+            .map_err(Error::Decompress)?;
+        Ok(decoded.into())
+    } else {
+        Ok(bytes)
+    }
+}
+
+/// Decompress a stream of gzip compressed chunks as they arrive
+fn gunzip_stream(stream: BoxStream<'static, Result<Bytes>>) -> BoxStream<'static, Result<Bytes>> {
+    let decoder = Some(write::GzDecoder::new(Vec::new()));
+    futures::stream::try_unfold((stream, decoder), |(mut stream, mut decoder)| async move {
+        let Some(mut d) = decoder.take() else {
+            return Ok(None);
+        };
+        let decoded = match stream.try_next().await? {
+            Some(chunk) => {
+                d.write_all(&chunk).map_err(Error::Decompress)?;
+                let decoded = std::mem::take(d.get_mut());
+                decoder = Some(d);
+                decoded
+            }
+            // the stream is exhausted, so flush whatever remains in the decoder
+            None => d.finish().map_err(Error::Decompress)?,
+        };
+        Ok(Some((Bytes::from(decoded), (stream, decoder))))
+    })
+    .boxed()
+}
+
 #[doc(hidden)]
 /// Typestate type for [`WriteRequestBuilder`]
 #[derive(Debug, Copy, Clone)]
@@ -1234,12 +1814,48 @@ impl QueryRequestBuilder<'_> {
 
     /// Send the request to `/api/v3/query_sql` or `/api/v3/query_influxql`
     pub async fn send(self) -> Result<Bytes> {
-        let url = match self.kind {
-            QueryKind::Sql => "/api/v3/query_sql",
-            QueryKind::InfluxQl => "/api/v3/query_influxql",
-        };
         self.client
-            .send_json_get_bytes(Method::POST, url, Some(self.request), None::<()>, None)
+            .send_json_get_bytes(
+                Method::POST,
+                self.kind.url_path(),
+                Some(self.request),
+                None::<()>,
+                None,
+            )
+            .await
+    }
+
+    /// Send the request to `/api/v3/query_sql` or `/api/v3/query_influxql`, returning a stream
+    /// over the chunks of the response body as they arrive, rather than buffering the entire
+    /// response in memory as [`send`][QueryRequestBuilder::send] does
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use influxdb3_client::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let client = Client::new("http://localhost:8181", None)?;
+    /// let mut stream = client
+    ///     .api_v3_query_sql("db_name", "SELECT * FROM foo")
+    ///     .send_stream()
+    ///     .await?;
+    /// while let Some(chunk) = stream.try_next().await? {
+    ///     // process the chunk
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_stream(self) -> Result<BoxStream<'static, Result<Bytes>>> {
+        self.client
+            .send_json_get_stream(
+                Method::POST,
+                self.kind.url_path(),
+                Some(self.request),
+                None::<()>,
+                None,
+            )
             .await
     }
 }
@@ -1251,6 +1867,15 @@ pub enum QueryKind {
     InfluxQl,
 }
 
+impl QueryKind {
+    fn url_path(&self) -> &'static str {
+        match self {
+            QueryKind::Sql => "/api/v3/query_sql",
+            QueryKind::InfluxQl => "/api/v3/query_influxql",
+        }
+    }
+}
+
 impl Display for QueryKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1416,11 +2041,18 @@ impl<'c> CreateDistinctCacheRequestBuilder<'c> {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use flate2::{Compression, write::GzEncoder};
+    use futures::TryStreamExt;
     use influxdb3_types::http::{LastCacheSize, LastCacheTtl};
     use mockito::{Matcher, Server};
+    use reqwest::StatusCode;
     use serde_json::json;
 
-    use crate::{Client, Precision, QueryFormat};
+    use crate::{Client, Precision, QueryFormat, RetryConfig};
 
     #[tokio::test]
     async fn api_v3_write_lp() {
@@ -1459,6 +2091,157 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn api_v3_write_lp_retry() {
+        let db = "stats";
+        let body = "cpu,host=s1 usage=0.5";
+
+        let mut mock_server = Server::new_async().await;
+        let unavailable = mock_server
+            .mock("POST", "/api/v3/write_lp")
+            .match_body(body)
+            .with_status(503)
+            // capped at the max backoff, so the test does not wait for a day
+            .with_header("retry-after", "86400")
+            .expect(2)
+            .create_async()
+            .await;
+        let success = mock_server
+            .mock("POST", "/api/v3/write_lp")
+            .match_body(body)
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None).expect("create client");
+        let retry = RetryConfig {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            client
+                .api_v3_write_lp(db)
+                .with_retry(retry)
+                .body(body)
+                .send(),
+        )
+        .await
+        .expect("the retry-after delay should be capped at the max backoff")
+        .expect("send write_lp request");
+
+        unavailable.assert_async().await;
+        success.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn api_v3_write_lp_no_retry_on_client_error() {
+        let db = "stats";
+        let body = "not line protocol";
+
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/write_lp")
+            .with_status(400)
+            .with_body("invalid line protocol")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None).expect("create client");
+
+        let err = client
+            .api_v3_write_lp(db)
+            .with_retry(RetryConfig::default())
+            .body(body)
+            .send()
+            .await
+            .unwrap_err();
+        let crate::Error::ApiError { code, .. } = err else {
+            panic!("expected an API error, got: {err}");
+        };
+        assert_eq!(StatusCode::BAD_REQUEST, code);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn api_v3_write_lp_compressed() {
+        let db = "stats";
+        let body = "cpu,host=s1 usage=0.5";
+
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/write_lp")
+            .match_header("Content-Encoding", "gzip")
+            .match_body(gzip(body.as_bytes()))
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None)
+            .expect("create client")
+            .with_compression(true);
+
+        client
+            .api_v3_write_lp(db)
+            .body(body)
+            .send()
+            .await
+            .expect("send write_lp request");
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn api_v3_query_sql_compressed() {
+        let db = "stats";
+        let query = "SELECT * FROM foo";
+        let body = r#"[{"host": "foo", "time": "1990-07-23T06:00:00:000", "val": 1}]"#;
+
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/query_sql")
+            .match_header("Accept-Encoding", "gzip")
+            .with_status(200)
+            .with_header("Content-Encoding", "gzip")
+            .with_body(gzip(body.as_bytes()))
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None)
+            .expect("create client")
+            .with_compression(true);
+
+        let r = client
+            .api_v3_query_sql(db, query)
+            .send()
+            .await
+            .expect("send request to server");
+        assert_eq!(&r, body);
+
+        let chunks: Vec<Bytes> = client
+            .api_v3_query_sql(db, query)
+            .send_stream()
+            .await
+            .expect("send request to server")
+            .try_collect()
+            .await
+            .expect("read response body");
+        assert_eq!(chunks.concat(), body.as_bytes());
+
+        mock.assert_async().await;
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
     #[tokio::test]
     async fn api_v3_query_sql() {
         let token = "super-secret-token";
@@ -1500,6 +2283,65 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn api_v3_query_sql_stream() {
+        let db = "stats";
+        let query = "SELECT * FROM foo";
+        let body = r#"[{"host": "foo", "time": "1990-07-23T06:00:00:000", "val": 1}]"#;
+
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/query_sql")
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None).expect("create client");
+
+        let chunks: Vec<Bytes> = client
+            .api_v3_query_sql(db, query)
+            .format(QueryFormat::Json)
+            .send_stream()
+            .await
+            .expect("send request to server")
+            .try_collect()
+            .await
+            .expect("read response body");
+
+        assert_eq!(chunks.concat(), body.as_bytes());
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn api_v3_query_sql_stream_error() {
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/query_sql")
+            .with_status(400)
+            .with_body("invalid query")
+            .create_async()
+            .await;
+
+        let client = Client::new(mock_server.url(), None).expect("create client");
+
+        let err = client
+            .api_v3_query_sql("stats", "SELEC")
+            .send_stream()
+            .await
+            .map(|_| ())
+            .unwrap_err();
+
+        let crate::Error::ApiError { code, message } = &err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(code.as_u16(), 400);
+        assert_eq!(message, "invalid query");
+
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn api_v3_query_sql_params() {
         let db = "stats";
@@ -1761,4 +2603,124 @@ mod tests {
             .unwrap();
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn custom_headers() {
+        let token = "super-secret-token";
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("GET", "/ping")
+            .match_header("Authorization", format!("Bearer {token}").as_str())
+            .match_header("X-Tenant-Id", "tenant-a")
+            .match_header("X-Other", "other")
+            .with_status(200)
+            .with_body(r#"{"version": "3.0.0", "revision": "abc", "process_id": "00000000-0000-0000-0000-000000000000"}"#)
+            .create_async()
+            .await;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-other", "other".parse().unwrap());
+        let client = Client::new(mock_server.url(), None)
+            .unwrap()
+            .with_auth_token(token)
+            .with_header("x-tenant-id".parse().unwrap(), "tenant-a".parse().unwrap())
+            .with_headers(headers);
+        client.ping().await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn connection_pool_options() {
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("GET", "/ping")
+            .match_header("Authorization", "Bearer token")
+            .with_status(200)
+            .with_body(r#"{"version": "3.0.0", "revision": "abc", "process_id": "00000000-0000-0000-0000-000000000000"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        // rebuilding the underlying client keeps the options that were already set
+        let client = Client::new(mock_server.url(), None)
+            .unwrap()
+            .with_auth_token("token")
+            .with_pool_max_idle_per_host(1)
+            .unwrap()
+            .with_pool_idle_timeout(Duration::from_secs(30))
+            .unwrap()
+            .with_tcp_keepalive(Duration::from_secs(15))
+            .unwrap()
+            .with_connect_timeout(Duration::from_secs(5))
+            .unwrap()
+            .with_read_timeout(Duration::from_secs(5))
+            .unwrap();
+        client.ping().await.unwrap();
+        client.clone().ping().await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn invalid_client_identity() {
+        let client = Client::new("https://localhost:8181", None).unwrap();
+        assert!(matches!(
+            client.with_client_identity(b"not a cert", b"not a key"),
+            Err(crate::Error::Builder(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // accept connections but never respond to them
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _handle = tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+        let client = Client::new(format!("http://{addr}"), None)
+            .unwrap()
+            .with_timeout(Duration::from_millis(100));
+        let err = client.ping().await.unwrap_err();
+        assert!(matches!(err, crate::Error::Timeout { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn stream_outlasts_request_timeout() {
+        let mut mock_server = Server::new_async().await;
+        let mock = mock_server
+            .mock("POST", "/api/v3/query_sql")
+            .with_status(200)
+            .with_chunked_body(|w| {
+                for line in ["a\n", "b\n", "c\n"] {
+                    std::thread::sleep(Duration::from_millis(60));
+                    w.write_all(line.as_bytes())?;
+                }
+                Ok(())
+            })
+            .create_async()
+            .await;
+
+        // the body takes longer than the request timeout to arrive, but no single read does
+        let client = Client::new(mock_server.url(), None)
+            .unwrap()
+            .with_timeout(Duration::from_millis(100))
+            .with_read_timeout(Duration::from_secs(5))
+            .unwrap();
+        let chunks = client
+            .api_v3_query_sql("foo", "SELECT * FROM bar")
+            .send_stream()
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(b"a\nb\nc\n".as_slice(), chunks.concat().as_slice());
+
+        mock.assert_async().await;
+    }
 }
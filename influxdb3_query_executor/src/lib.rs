@@ -157,21 +157,24 @@ impl QueryExecutor for QueryExecutorImpl {
     ) -> Result<SendableRecordBatchStream, QueryExecutorError> {
         info!(%database, %query, ?params, "executing sql query");
         let db = self.get_db_namespace(database, &span_ctx).await?;
-        let span_ctxt = span_ctx
-            .clone()
-            .map(|span| span.child("query_database_sql"));
+        let span_ctxt = span_ctx.map(|span| span.child("query_database_sql"));
         let mut recorder = SpanRecorder::new(span_ctxt);
+        recorder.set_metadata("db_name", MetaValue::String(database.to_string().into()));
         recorder.set_metadata("query", MetaValue::String(query.to_string().into()));
 
-        query_database_sql(
+        // the recorder is kept until the query has been planned, so that its span covers the
+        // planning, which is recorded in spans of its own under it
+        let result = query_database_sql(
             db,
             query,
             params,
-            span_ctx,
+            recorder.span().map(|span| span.ctx.clone()),
             external_span_ctx,
             Arc::clone(&self.telemetry_store),
         )
-        .await
+        .await;
+        record_result(&mut recorder, &result);
+        result
     }
 
     async fn query_influxql(
@@ -185,16 +188,23 @@ impl QueryExecutor for QueryExecutorImpl {
     ) -> Result<SendableRecordBatchStream, QueryExecutorError> {
         info!(database, query, ?params, "executing influxql query");
         let db = self.get_db_namespace(database, &span_ctx).await?;
-        query_database_influxql(
+        let span_ctxt = span_ctx.map(|span| span.child("query_database_influxql"));
+        let mut recorder = SpanRecorder::new(span_ctxt);
+        recorder.set_metadata("db_name", MetaValue::String(database.to_string().into()));
+        recorder.set_metadata("query", MetaValue::String(query.to_string().into()));
+
+        let result = query_database_influxql(
             db,
             query,
             influxql_statement,
             params,
-            span_ctx,
+            recorder.span().map(|span| span.ctx.clone()),
             external_span_ctx,
             Arc::clone(&self.telemetry_store),
         )
-        .await
+        .await;
+        record_result(&mut recorder, &result);
+        result
     }
 
     fn show_databases(
@@ -284,6 +294,14 @@ impl QueryExecutor for QueryExecutorImpl {
     }
 }
 
+/// Mark the span for a query as successful once the query has started to execute, or as failed
+fn record_result<T>(recorder: &mut SpanRecorder, result: &Result<T, QueryExecutorError>) {
+    match result {
+        Ok(_) => recorder.ok("query started"),
+        Err(e) => recorder.error(e.to_string()),
+    }
+}
+
 // NOTE: this method is separated out as it is called from a separate query executor
 // implementation in Enterprise
 async fn query_database_sql(
@@ -1520,4 +1538,68 @@ mod tests {
             assert_batches_sorted_eq!(expected, &batches);
         }
     }
+
+    #[test_log::test(tokio::test)]
+    async fn test_influxql_query_span() {
+        use influxdb3_write::test_helpers::do_write;
+        use iox_query_influxql_rewrite as rewrite;
+        use trace::{RingBufferTraceCollector, ctx::SpanContext, span::MetaValue};
+
+        let (wb, qe, tp, ..) = setup(None, false).await;
+        do_write(wb.as_ref(), "test_db", "test_table,t1=a val=1", tp.now()).await;
+
+        let collector = Arc::new(RingBufferTraceCollector::new(100));
+        let span_ctx = SpanContext::new(Arc::clone(&collector) as _);
+        let query_str = "select * from test_table";
+        let statement = rewrite::parse_statements(query_str)
+            .unwrap()
+            .pop()
+            .unwrap()
+            .to_statement();
+        let stream = qe
+            .query_influxql("test_db", query_str, statement, None, Some(span_ctx), None)
+            .await
+            .expect("query should work");
+        let _: Vec<RecordBatch> = stream.try_collect().await.unwrap();
+
+        let spans = collector.spans();
+        let span = spans
+            .iter()
+            .find(|s| s.name == "query_database_influxql")
+            .expect("the query should have been recorded in a span");
+        assert!(
+            matches!(span.metadata.get("db_name"), Some(MetaValue::String(db)) if db == "test_db"),
+            "unexpected span metadata: {:?}",
+            span.metadata
+        );
+        // planning the query is recorded within the query's span
+        assert!(
+            spans
+                .iter()
+                .any(|s| s.ctx.parent_span_id == Some(span.ctx.span_id)),
+            "expected child spans of {span:?} in: {spans:?}"
+        );
+
+        // the single write is buffered in one chunk, which the query is planned with
+        let table_chunks = spans
+            .iter()
+            .find(|s| s.name == "table_chunks")
+            .unwrap_or_else(|| panic!("expected a table_chunks span in: {spans:?}"));
+        let metadata = &table_chunks.metadata;
+        assert!(
+            matches!(metadata.get("db_name"), Some(MetaValue::String(db)) if db == "test_db"),
+            "unexpected span metadata: {metadata:?}"
+        );
+        assert!(
+            matches!(
+                metadata.get("table_name"),
+                Some(MetaValue::String(table)) if table == "test_table"
+            ),
+            "unexpected span metadata: {metadata:?}"
+        );
+        assert!(
+            matches!(metadata.get("chunk_count"), Some(MetaValue::Int(1))),
+            "unexpected span metadata: {metadata:?}"
+        );
+    }
 }
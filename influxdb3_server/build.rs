@@ -0,0 +1,16 @@
+// Compile the descriptor set for the Flight service, which is registered with the gRPC server
+// reflection service
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("protos");
+    let proto = root.join("Flight.proto");
+    println!("cargo:rerun-if-changed={}", proto.display());
+
+    let descriptor_path = PathBuf::from(std::env::var("OUT_DIR")?).join("flight_descriptor.bin");
+    prost_build::Config::new()
+        .file_descriptor_set_path(descriptor_path)
+        .compile_protos(&[proto], &[root])?;
+
+    Ok(())
+}
@@ -1,15 +1,205 @@
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use arrow_flight::flight_service_server::{
     FlightService as Flight, FlightServiceServer as FlightServer,
 };
 use authz::Authorizer;
+use influxdb3_authz::{AuthProvider, CachingAuthorizer};
 use influxdb3_internal_api::query_executor::QueryExecutor;
+use iox_time::SystemProvider;
+use observability_deps::tracing::{info, warn};
+use tonic::codec::CompressionEncoding;
+use tonic::server::NamedService;
+use tonic::service::Routes;
+use tonic_health::{ServingStatus, server::HealthReporter};
+
+use self::audit::FlightAudit;
+use self::bypass::AuthzBypass;
+use self::limit::{ConcurrencyLimit, Permits};
+
+pub use self::audit::{FlightAuditor, FlightRequestDescriptor, FlightRequestInfo};
+pub use self::bypass::FlightMethod;
+
+mod audit;
+mod bypass;
+mod limit;
+
+/// The encoded descriptor set for `arrow.flight.protocol.FlightService`, built from
+/// `protos/Flight.proto`
+const FLIGHT_FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/flight_descriptor.bin"));
+
+/// Options for the services exposed by the gRPC server
+#[derive(Debug, Clone, Default)]
+pub struct GrpcConfig {
+    /// Register the gRPC server reflection service, so that tools like `grpcurl` can discover
+    /// the services offered by the server
+    pub reflection: bool,
+    /// Cache successful token authorizations for Flight requests, see [`CachingAuthorizer`]
+    pub authz_cache: Option<AuthzCacheConfig>,
+    /// The encodings that Flight responses may be compressed with
+    pub compression: GrpcCompressionConfig,
+    /// The maximum number of Flight requests that can be in flight at once, further requests are
+    /// rejected with `RESOURCE_EXHAUSTED`, see [`ConcurrencyLimit`]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+    /// Flight methods that are served without checking the request's token, see [`AuthzBypass`]
+    pub authz_bypass: Vec<FlightMethod>,
+}
+
+/// The encodings that Flight responses may be compressed with
+///
+/// A response is only compressed if the client advertises that it accepts one of the enabled
+/// encodings in its `grpc-accept-encoding` header, so clients that do not ask for compression are
+/// unaffected. Compressed requests are always accepted, regardless of these options.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrpcCompressionConfig {
+    pub gzip: bool,
+    pub zstd: bool,
+}
+
+/// Options for caching the authorizations made by the Flight service
+#[derive(Debug, Clone, Copy)]
+pub struct AuthzCacheConfig {
+    /// How long an authorization is cached for
+    pub ttl: Duration,
+    /// The maximum number of tokens to cache authorizations for
+    pub capacity: NonZeroUsize,
+}
+
+/// Wrap the authorizer in a [`CachingAuthorizer`] if the authorization cache is configured
+fn flight_authorizer(
+    authz: Option<Arc<dyn Authorizer>>,
+    config: &GrpcConfig,
+) -> Option<Arc<dyn Authorizer>> {
+    match (authz, config.authz_cache) {
+        (Some(authz), Some(AuthzCacheConfig { ttl, capacity })) => {
+            info!(
+                ?ttl,
+                capacity = capacity.get(),
+                "caching flight authorizations"
+            );
+            Some(Arc::new(CachingAuthorizer::new(
+                authz,
+                Arc::new(SystemProvider::new()),
+                ttl,
+                capacity,
+            )) as _)
+        }
+        (authz, _) => authz,
+    }
+}
 
 pub(crate) fn make_flight_server(
     server: Arc<dyn QueryExecutor>,
     authz: Option<Arc<dyn Authorizer>>,
+    config: &GrpcConfig,
 ) -> FlightServer<impl Flight> {
     let query_db = server.upcast();
-    service_grpc_flight::make_server(query_db, authz)
+    let mut flight = service_grpc_flight::make_server(query_db, authz)
+        .accept_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Zstd);
+    let GrpcCompressionConfig { gzip, zstd } = config.compression;
+    if gzip {
+        flight = flight.send_compressed(CompressionEncoding::Gzip);
+    }
+    if zstd {
+        flight = flight.send_compressed(CompressionEncoding::Zstd);
+    }
+    flight
+}
+
+/// Assemble the gRPC services served alongside the HTTP API
+///
+/// This includes a `grpc.health.v1.Health` service, whose status is reported through the
+/// returned [`GrpcHealth`]. All services start out as `NOT_SERVING`. If an `auditor` is given, it
+/// is passed information about every Flight request, including those rejected by the concurrency
+/// limit. Requests for the methods in [`GrpcConfig::authz_bypass`] are served by a Flight service
+/// that has no authorizer.
+pub(crate) async fn make_grpc_service(
+    server: Arc<dyn QueryExecutor>,
+    auth: Option<Arc<dyn AuthProvider>>,
+    config: GrpcConfig,
+    auditor: Option<Arc<dyn FlightAuditor>>,
+) -> (Routes, GrpcHealth) {
+    let permits = config.max_concurrent_requests.map(|limit| {
+        info!(limit = limit.get(), "limiting concurrent flight requests");
+        Permits::new(limit)
+    });
+    let authz = flight_authorizer(auth.as_ref().map(|auth| auth.upcast()), &config);
+    let unauthorized = if config.authz_bypass.is_empty() || authz.is_none() {
+        None
+    } else {
+        warn!(
+            methods = ?config.authz_bypass,
+            "flight methods will be served without authorization"
+        );
+        Some(ConcurrencyLimit::new(
+            make_flight_server(Arc::clone(&server), None, &config),
+            permits.clone(),
+        ))
+    };
+    // authorized requests only take a permit once their token has been accepted
+    let authorized = match (authz, &permits) {
+        (Some(authz), Some(limit)) => ConcurrencyLimit::on_authorization(
+            make_flight_server(server, Some(limit.authorizer(authz)), &config),
+            permits.clone(),
+        ),
+        (authz, _) => {
+            ConcurrencyLimit::new(make_flight_server(server, authz, &config), permits.clone())
+        }
+    };
+    let flight = AuthzBypass::new(authorized, unauthorized, &config.authz_bypass);
+    let (reporter, health_service) = tonic_health::server::health_reporter();
+    let mut health = GrpcHealth {
+        reporter,
+        services: vec![service_name(&flight)],
+    };
+    health.set_serving(false).await;
+
+    let mut routes =
+        Routes::new(FlightAudit::new(flight, auditor, auth)).add_service(health_service);
+    if config.reflection {
+        // only the services that are served are registered, the reflection service describes
+        // itself. It only fails to build if a descriptor set cannot be decoded, which should not
+        // prevent the rest of the gRPC services from being served
+        match tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(FLIGHT_FILE_DESCRIPTOR_SET)
+            .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+            .build_v1()
+        {
+            Ok(reflection) => {
+                info!("gRPC server reflection enabled");
+                routes = routes.add_service(reflection);
+            }
+            Err(error) => warn!(%error, "failed to build the gRPC reflection service"),
+        }
+    }
+    (routes, health)
+}
+
+fn service_name<S: NamedService>(_: &S) -> &'static str {
+    S::NAME
+}
+
+/// Reports the status of the gRPC services through the `grpc.health.v1.Health` service
+#[derive(Clone)]
+pub(crate) struct GrpcHealth {
+    reporter: HealthReporter,
+    /// The services that the status is reported for, in addition to the server as a whole
+    services: Vec<&'static str>,
+}
+
+impl GrpcHealth {
+    /// Report the server, and each of its services, as `SERVING` or `NOT_SERVING`
+    pub(crate) async fn set_serving(&mut self, serving: bool) {
+        let status = if serving {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        };
+        // the empty service name is used to report the status of the server as a whole
+        for service in std::iter::once("").chain(self.services.iter().copied()) {
+            self.reporter.set_service_status(service, status).await;
+        }
+    }
 }
@@ -0,0 +1,348 @@
+use std::convert::Infallible;
+use std::fmt::Debug;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow_flight::{FlightDescriptor, Ticket};
+use bytes::Bytes;
+use http::{Request, Response, header::AUTHORIZATION};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use influxdb3_authz::AuthProvider;
+use influxdb3_id::TokenId;
+use prost::Message;
+use tonic::Status;
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tower::Service;
+
+/// The largest request message that is buffered to be audited, which is the largest message that
+/// tonic decodes by default
+const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Each message in a gRPC request body is prefixed with a compression flag and its length
+const MESSAGE_PREFIX_SIZE: usize = 5;
+
+/// Receives information about every request made to the Flight service, e.g., to emit audit logs
+///
+/// [`FlightAuditor::audit`] is called before the request is handled, so it is called for requests
+/// that go on to be rejected as well as for those that succeed.
+pub trait FlightAuditor: Debug + Send + Sync + 'static {
+    fn audit(&self, info: FlightRequestInfo);
+}
+
+/// Information about a request made to the Flight service
+#[derive(Debug, Clone)]
+pub struct FlightRequestInfo {
+    /// The Flight method that was called, e.g., `DoGet`
+    pub method: String,
+    /// The address of the client that made the request, if known
+    pub peer_addr: Option<SocketAddr>,
+    /// The token the request was authenticated with, or `None` if the request does not have a
+    /// valid token
+    pub token_id: Option<TokenId>,
+    /// What the request asks for, for the methods that take a single request message
+    pub descriptor: Option<FlightRequestDescriptor>,
+}
+
+/// What a Flight request asks for
+#[derive(Debug, Clone)]
+pub enum FlightRequestDescriptor {
+    /// The ticket passed to `DoGet`, which for queries holds the database and the query
+    Ticket(Bytes),
+    /// The descriptor passed to `GetFlightInfo`, `PollFlightInfo`, or `GetSchema`
+    Flight(FlightDescriptor),
+}
+
+/// Passes information about each request made to the wrapped Flight service to a
+/// [`FlightAuditor`]
+#[derive(Debug, Clone)]
+pub(crate) struct FlightAudit<S> {
+    inner: S,
+    auditor: Option<Arc<dyn FlightAuditor>>,
+    auth: Option<Arc<dyn AuthProvider>>,
+}
+
+impl<S> FlightAudit<S> {
+    /// Wrap `inner`, requests are passed straight through if there is no `auditor`
+    pub(crate) fn new(
+        inner: S,
+        auditor: Option<Arc<dyn FlightAuditor>>,
+        auth: Option<Arc<dyn AuthProvider>>,
+    ) -> Self {
+        Self {
+            inner,
+            auditor,
+            auth,
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for FlightAudit<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<Request<BoxBody>> for FlightAudit<S>
+where
+    S: Service<Request<BoxBody>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<BoxBody>) -> Self::Future {
+        // the clone may not be ready, so use it in place of the service that is
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let Some(auditor) = self.auditor.clone() else {
+            return Box::pin(inner.call(req));
+        };
+        let auth = self.auth.clone();
+
+        Box::pin(async move {
+            let method = req
+                .uri()
+                .path()
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let peer_addr = req
+                .extensions()
+                .get::<Option<SocketAddr>>()
+                .copied()
+                .flatten();
+            let token_id = match auth {
+                Some(auth) => auth.authenticate(extract_token(&req)).await.ok(),
+                None => None,
+            };
+
+            // the methods that take a single message have their request body read up front so
+            // that the message can be decoded, and then passed on to the wrapped service
+            let (req, descriptor) = match method.as_str() {
+                "DoGet" | "GetFlightInfo" | "PollFlightInfo" | "GetSchema" => {
+                    match buffer_message(&method, req).await {
+                        Ok((req, descriptor)) => (Ok(req), descriptor),
+                        Err(status) => (Err(status), None),
+                    }
+                }
+                _ => (Ok(req), None),
+            };
+
+            auditor.audit(FlightRequestInfo {
+                method,
+                peer_addr,
+                token_id,
+                descriptor,
+            });
+            match req {
+                Ok(req) => inner.call(req).await,
+                Err(status) => Ok(status.into_http()),
+            }
+        })
+    }
+}
+
+/// Get the token from the `authorization` header of a gRPC request, which is expected to be in
+/// the form `Bearer <token>` or `Token <token>`
+fn extract_token<B>(req: &Request<B>) -> Option<Vec<u8>> {
+    let value = req.headers().get(AUTHORIZATION)?.as_bytes();
+    let (scheme, token) = value.split_at(value.iter().position(|b| *b == b' ')?);
+    matches!(scheme, b"Bearer" | b"Token").then(|| token[1..].to_vec())
+}
+
+/// Read the body of a request that holds a single message, decoding the message
+///
+/// The request is rejected, as tonic would reject it, if its body is larger than a message is
+/// allowed to be, so that it is never buffered in full.
+async fn buffer_message(
+    method: &str,
+    req: Request<BoxBody>,
+) -> Result<(Request<BoxBody>, Option<FlightRequestDescriptor>), Status> {
+    let (parts, body) = req.into_parts();
+    let body = Limited::new(body, MAX_MESSAGE_SIZE + MESSAGE_PREFIX_SIZE)
+        .collect()
+        .await
+        .map_err(|e| match e.downcast::<Status>() {
+            Ok(status) => *status,
+            Err(e) if e.is::<LengthLimitError>() => Status::resource_exhausted(format!(
+                "request message is larger than the limit of {MAX_MESSAGE_SIZE} bytes"
+            )),
+            Err(e) => Status::internal(e.to_string()),
+        })?
+        .to_bytes();
+    let descriptor = decode_descriptor(method, &body);
+    let body = tonic::body::boxed(Full::new(body));
+    Ok((Request::from_parts(parts, body), descriptor))
+}
+
+/// Decode the message in the body of a request to the given Flight method
+///
+/// This is best effort, `None` is returned for a compressed or malformed message, which the
+/// wrapped service then handles, or rejects, as it normally would.
+fn decode_descriptor(method: &str, body: &[u8]) -> Option<FlightRequestDescriptor> {
+    // messages are prefixed with a compression flag and their length as a big endian `u32`
+    let (&[compressed, l0, l1, l2, l3], message) =
+        body.split_first_chunk::<MESSAGE_PREFIX_SIZE>()?;
+    let message = message.get(..u32::from_be_bytes([l0, l1, l2, l3]) as usize)?;
+    if compressed != 0 {
+        return None;
+    }
+    match method {
+        "DoGet" => Ticket::decode(message)
+            .ok()
+            .map(|ticket| FlightRequestDescriptor::Ticket(ticket.ticket)),
+        _ => FlightDescriptor::decode(message)
+            .ok()
+            .map(FlightRequestDescriptor::Flight),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use arrow_flight::Ticket;
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use http_body_util::{BodyExt, Full};
+    use prost::Message;
+    use tonic::Code;
+    use tonic::body::BoxBody;
+    use tower::{Service, ServiceExt};
+
+    use super::{
+        FlightAudit, FlightAuditor, FlightRequestDescriptor, FlightRequestInfo, MAX_MESSAGE_SIZE,
+        extract_token,
+    };
+
+    #[derive(Debug, Default)]
+    struct RecordingAuditor {
+        requests: Mutex<Vec<FlightRequestInfo>>,
+    }
+
+    impl FlightAuditor for RecordingAuditor {
+        fn audit(&self, info: FlightRequestInfo) {
+            self.requests.lock().unwrap().push(info);
+        }
+    }
+
+    fn grpc_message(message: impl Message) -> Bytes {
+        let message = message.encode_to_vec();
+        let mut body = vec![0];
+        body.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        body.extend_from_slice(&message);
+        body.into()
+    }
+
+    #[tokio::test]
+    async fn test_flight_audit() {
+        // the wrapped service echoes the request body, to check that it is passed on intact
+        let inner = tower::service_fn(|req: Request<BoxBody>| async {
+            let body = req.into_body().collect().await.unwrap().to_bytes();
+            Ok::<_, Infallible>(Response::new(tonic::body::boxed(Full::new(body))))
+        });
+        let auditor = Arc::new(RecordingAuditor::default());
+        let mut service = FlightAudit::new(inner, Some(Arc::clone(&auditor) as _), None);
+
+        let peer_addr: SocketAddr = "127.0.0.1:1234".parse().unwrap();
+        let body = grpc_message(Ticket::new("select 1"));
+        let mut req = Request::builder()
+            .uri("/arrow.flight.protocol.FlightService/DoGet")
+            .body(tonic::body::boxed(Full::new(body.clone())))
+            .unwrap();
+        req.extensions_mut().insert(Some(peer_addr));
+        let response = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(
+            body,
+            response.into_body().collect().await.unwrap().to_bytes()
+        );
+
+        let req = Request::builder()
+            .uri("/arrow.flight.protocol.FlightService/DoPut")
+            .body(tonic::body::empty_body())
+            .unwrap();
+        service.ready().await.unwrap().call(req).await.unwrap();
+
+        let requests = auditor.requests.lock().unwrap();
+        let [do_get, do_put] = requests.as_slice() else {
+            panic!("expected two audited requests, got: {requests:?}");
+        };
+        assert_eq!("DoGet", do_get.method);
+        assert_eq!(Some(peer_addr), do_get.peer_addr);
+        assert!(do_get.token_id.is_none());
+        let Some(FlightRequestDescriptor::Ticket(ticket)) = &do_get.descriptor else {
+            panic!("expected a ticket, got: {:?}", do_get.descriptor);
+        };
+        assert_eq!(&Bytes::from("select 1"), ticket);
+        assert_eq!("DoPut", do_put.method);
+        assert!(do_put.peer_addr.is_none());
+        assert!(do_put.descriptor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_flight_audit_rejects_large_messages() {
+        // a request that reached the wrapped service would get a response without a status
+        let inner = tower::service_fn(|_: Request<BoxBody>| async {
+            Ok::<_, Infallible>(Response::new(tonic::body::empty_body()))
+        });
+        let auditor = Arc::new(RecordingAuditor::default());
+        let mut service = FlightAudit::new(inner, Some(Arc::clone(&auditor) as _), None);
+
+        let body = grpc_message(Ticket::new(vec![0; MAX_MESSAGE_SIZE + 1]));
+        let req = Request::builder()
+            .uri("/arrow.flight.protocol.FlightService/DoGet")
+            .body(tonic::body::boxed(Full::new(body)))
+            .unwrap();
+        let response = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(
+            Some(Code::ResourceExhausted),
+            response
+                .headers()
+                .get("grpc-status")
+                .map(|status| Code::from_bytes(status.as_bytes()))
+        );
+
+        // rejected requests are still audited, without their descriptor
+        let requests = auditor.requests.lock().unwrap();
+        let [do_get] = requests.as_slice() else {
+            panic!("expected one audited request, got: {requests:?}");
+        };
+        assert_eq!("DoGet", do_get.method);
+        assert!(do_get.descriptor.is_none());
+    }
+
+    #[test]
+    fn test_extract_token() {
+        let req = |value: Option<&str>| {
+            let mut builder = Request::builder();
+            if let Some(value) = value {
+                builder = builder.header("authorization", value);
+            }
+            builder.body(()).unwrap()
+        };
+        assert_eq!(
+            Some(b"abc".to_vec()),
+            extract_token(&req(Some("Bearer abc")))
+        );
+        assert_eq!(
+            Some(b"abc".to_vec()),
+            extract_token(&req(Some("Token abc")))
+        );
+        assert_eq!(None, extract_token(&req(Some("Basic abc"))));
+        assert_eq!(None, extract_token(&req(Some("abc"))));
+        assert_eq!(None, extract_token(&req(None)));
+    }
+}
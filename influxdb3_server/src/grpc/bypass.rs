@@ -0,0 +1,273 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use http::{Request, Response};
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tower::Service;
+
+/// A method of the Flight service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlightMethod {
+    Handshake,
+    ListFlights,
+    GetFlightInfo,
+    PollFlightInfo,
+    GetSchema,
+    DoGet,
+    DoPut,
+    DoExchange,
+    DoAction,
+    ListActions,
+}
+
+impl FlightMethod {
+    const ALL: [Self; 10] = [
+        Self::Handshake,
+        Self::ListFlights,
+        Self::GetFlightInfo,
+        Self::PollFlightInfo,
+        Self::GetSchema,
+        Self::DoGet,
+        Self::DoPut,
+        Self::DoExchange,
+        Self::DoAction,
+        Self::ListActions,
+    ];
+
+    /// The name of the method, as it appears in the path of a gRPC request
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Handshake => "Handshake",
+            Self::ListFlights => "ListFlights",
+            Self::GetFlightInfo => "GetFlightInfo",
+            Self::PollFlightInfo => "PollFlightInfo",
+            Self::GetSchema => "GetSchema",
+            Self::DoGet => "DoGet",
+            Self::DoPut => "DoPut",
+            Self::DoExchange => "DoExchange",
+            Self::DoAction => "DoAction",
+            Self::ListActions => "ListActions",
+        }
+    }
+}
+
+impl Display for FlightMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FlightMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|method| method.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(|method| method.name()).join(", ");
+                format!("'{s}' is not a Flight method, valid methods are {names}")
+            })
+    }
+}
+
+/// Sends requests for some Flight methods to a service that does not check the request's token
+///
+/// Requests for any other method are sent to the `authorized` service, which does. When there is
+/// no `unauthorized` service, every request is sent to the `authorized` service.
+#[derive(Debug, Clone)]
+pub(crate) struct AuthzBypass<S> {
+    authorized: S,
+    unauthorized: Option<S>,
+    methods: Arc<[FlightMethod]>,
+}
+
+impl<S> AuthzBypass<S> {
+    pub(crate) fn new(authorized: S, unauthorized: Option<S>, methods: &[FlightMethod]) -> Self {
+        Self {
+            authorized,
+            unauthorized,
+            methods: methods.into(),
+        }
+    }
+
+    fn bypasses<B>(&self, req: &Request<B>) -> bool {
+        let method = req.uri().path().rsplit('/').next().unwrap_or_default();
+        self.methods.iter().any(|m| m.name() == method)
+    }
+}
+
+impl<S: NamedService> NamedService for AuthzBypass<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<Request<BoxBody>> for AuthzBypass<S>
+where
+    S: Service<Request<BoxBody>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.authorized.poll_ready(cx))?;
+        match &mut self.unauthorized {
+            Some(unauthorized) => unauthorized.poll_ready(cx),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, req: Request<BoxBody>) -> Self::Future {
+        let bypasses = self.bypasses(&req);
+        let service = match &mut self.unauthorized {
+            Some(unauthorized) if bypasses => unauthorized,
+            _ => &mut self.authorized,
+        };
+        // the clone may not be ready, so use it in place of the service that is
+        let clone = service.clone();
+        let mut service = std::mem::replace(service, clone);
+        Box::pin(async move { service.call(req).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::num::NonZeroUsize;
+
+    use http::{Request, Response};
+    use tonic::Code;
+    use tonic::body::BoxBody;
+    use tower::{Service, ServiceExt};
+
+    use super::super::limit::{ConcurrencyLimit, Permits};
+    use super::{AuthzBypass, FlightMethod};
+
+    /// A service that responds with its name in a header, to tell which one was called
+    fn named_service(
+        name: &'static str,
+    ) -> impl Service<
+        Request<BoxBody>,
+        Response = Response<BoxBody>,
+        Error = Infallible,
+        Future: Send,
+    > + Clone
+    + Send
+    + 'static {
+        tower::service_fn(move |_: Request<BoxBody>| async move {
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .header("service", name)
+                    .body(tonic::body::empty_body())
+                    .unwrap(),
+            )
+        })
+    }
+
+    fn flight_request(method: &str) -> Request<BoxBody> {
+        Request::builder()
+            .uri(format!("/arrow.flight.protocol.FlightService/{method}"))
+            .body(tonic::body::empty_body())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_flight_method() {
+        assert_eq!(FlightMethod::Handshake, "Handshake".parse().unwrap());
+        assert_eq!(FlightMethod::DoGet, "DoGet".parse().unwrap());
+        let err = "do_get".parse::<FlightMethod>().unwrap_err();
+        assert!(err.starts_with("'do_get' is not a Flight method"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_authz_bypass() {
+        let called = |mut bypass: AuthzBypass<_>, method: &str| {
+            let req = flight_request(method);
+            async move {
+                let response = bypass.ready().await.unwrap().call(req).await.unwrap();
+                response.headers()["service"].to_str().unwrap().to_string()
+            }
+        };
+
+        let bypass = AuthzBypass::new(
+            named_service("authorized"),
+            Some(named_service("unauthorized")),
+            &[FlightMethod::Handshake],
+        );
+        assert_eq!("unauthorized", called(bypass.clone(), "Handshake").await);
+        assert_eq!("authorized", called(bypass.clone(), "DoGet").await);
+
+        // without an unauthorized service, nothing bypasses the authorizer
+        let bypass = AuthzBypass::new(
+            named_service("authorized"),
+            None,
+            &[FlightMethod::Handshake],
+        );
+        assert_eq!("authorized", called(bypass, "Handshake").await);
+    }
+
+    #[tokio::test]
+    async fn test_bypassed_requests_count_against_limit() {
+        // the limit is applied to both services, as it is by the gRPC server
+        let permits = Permits::new(NonZeroUsize::new(2).unwrap());
+        let mut service = AuthzBypass::new(
+            ConcurrencyLimit::new(named_service("authorized"), Some(permits.clone())),
+            Some(ConcurrencyLimit::new(
+                named_service("unauthorized"),
+                Some(permits),
+            )),
+            &[FlightMethod::Handshake],
+        );
+        let grpc_status = |response: &Response<_>| {
+            response
+                .headers()
+                .get("grpc-status")
+                .map(|status| Code::from_bytes(status.as_bytes()))
+        };
+
+        // requests without a token that bypass the authorizer hold their permits like any other
+        let mut held = Vec::new();
+        for _ in 0..2 {
+            let response = service
+                .ready()
+                .await
+                .unwrap()
+                .call(flight_request("Handshake"))
+                .await
+                .unwrap();
+            assert_eq!(None, grpc_status(&response));
+            assert_eq!("unauthorized", response.headers()["service"]);
+            held.push(response);
+        }
+        for method in ["Handshake", "DoGet"] {
+            let response = service
+                .ready()
+                .await
+                .unwrap()
+                .call(flight_request(method))
+                .await
+                .unwrap();
+            assert_eq!(Some(Code::ResourceExhausted), grpc_status(&response));
+        }
+
+        drop(held);
+        let response = service
+            .ready()
+            .await
+            .unwrap()
+            .call(flight_request("Handshake"))
+            .await
+            .unwrap();
+        assert_eq!(None, grpc_status(&response));
+    }
+}
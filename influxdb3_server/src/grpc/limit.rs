@@ -0,0 +1,358 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use authz::{Authorization, Authorizer, Error as AuthzError, Permission};
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use observability_deps::tracing::debug;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tonic::Status;
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tower::Service;
+
+tokio::task_local! {
+    /// Where a [`LimitedAuthorizer`] records whether the request it authorized was given a permit
+    static ADMISSION: Arc<Admission>;
+}
+
+/// The permit given to an authorized request, or `None` if there were none left
+type Admission = OnceLock<Option<OwnedSemaphorePermit>>;
+
+/// The permits for the requests that can be in flight at once, shared by each
+/// [`ConcurrencyLimit`] and [`LimitedAuthorizer`] that the limit applies to
+#[derive(Debug, Clone)]
+pub(crate) struct Permits {
+    semaphore: Arc<Semaphore>,
+    limit: NonZeroUsize,
+}
+
+impl Permits {
+    pub(crate) fn new(limit: NonZeroUsize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit.get())),
+            limit,
+        }
+    }
+
+    /// Wrap `authz` so that the requests it authorizes take a permit, see
+    /// [`ConcurrencyLimit::on_authorization`]
+    pub(crate) fn authorizer(&self, authz: Arc<dyn Authorizer>) -> Arc<dyn Authorizer> {
+        Arc::new(LimitedAuthorizer {
+            inner: authz,
+            permits: self.clone(),
+        })
+    }
+
+    fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = Arc::clone(&self.semaphore).try_acquire_owned().ok();
+        if permit.is_none() {
+            debug!(
+                limit = self.limit.get(),
+                "rejecting gRPC request, too many requests in flight"
+            );
+        }
+        permit
+    }
+
+    fn exhausted(&self) -> Response<BoxBody> {
+        Status::resource_exhausted(format!(
+            "too many concurrent requests, the server is limited to {}",
+            self.limit
+        ))
+        .into_http()
+    }
+}
+
+/// Limits the number of requests that are in flight on the wrapped gRPC service
+///
+/// A request that arrives while `limit` requests are already in flight is rejected immediately
+/// with `RESOURCE_EXHAUSTED`, rather than queued. A permit is held until the response body has
+/// been sent in full, so long running streams, e.g., from `DoGet`, count against the limit for as
+/// long as they are being sent. Requests are passed straight through when there are no permits.
+#[derive(Debug, Clone)]
+pub(crate) struct ConcurrencyLimit<S> {
+    inner: S,
+    permits: Option<Permits>,
+    on_authorization: bool,
+}
+
+impl<S> ConcurrencyLimit<S> {
+    /// Take a permit for every request, before it is passed to `inner`
+    ///
+    /// This is for services that do not authorize their requests, e.g., those serving the
+    /// methods that bypass the authorizer.
+    pub(crate) fn new(inner: S, permits: Option<Permits>) -> Self {
+        Self {
+            inner,
+            permits,
+            on_authorization: false,
+        }
+    }
+
+    /// Take a permit for each request once `inner` has authorized it, with an authorizer from
+    /// [`Permits::authorizer`]
+    ///
+    /// Requests are authorized before they take a permit, so requests with a missing or invalid
+    /// token are rejected as they would be without the limit, and cannot use up the permits that
+    /// authorized requests need. The authorizer takes the permit when the token is accepted, and
+    /// if there are none left, fails the authorization so that the request is not handled. Its
+    /// response is then replaced with `RESOURCE_EXHAUSTED`. That way, each request is only
+    /// authorized once, by `inner`.
+    pub(crate) fn on_authorization(inner: S, permits: Option<Permits>) -> Self {
+        Self {
+            inner,
+            permits,
+            on_authorization: true,
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for ConcurrencyLimit<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S, B> Service<Request<B>> for ConcurrencyLimit<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        // the clone may not be ready, so use it in place of the service that is
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let Some(permits) = self.permits.clone() else {
+            return Box::pin(inner.call(req));
+        };
+
+        if !self.on_authorization {
+            return Box::pin(async move {
+                let Some(permit) = permits.try_acquire() else {
+                    return Ok(permits.exhausted());
+                };
+                let response = inner.call(req).await?;
+                Ok(hold_permit(response, permit))
+            });
+        }
+
+        Box::pin(async move {
+            let admission = Arc::new(Admission::new());
+            let response = ADMISSION
+                .scope(Arc::clone(&admission), inner.call(req))
+                .await?;
+            let admission = Arc::into_inner(admission).and_then(OnceLock::into_inner);
+            Ok(match admission {
+                Some(Some(permit)) => hold_permit(response, permit),
+                Some(None) => permits.exhausted(),
+                // the request was not authorized, so it was rejected without taking a permit
+                None => response,
+            })
+        })
+    }
+}
+
+fn hold_permit(response: Response<BoxBody>, permit: OwnedSemaphorePermit) -> Response<BoxBody> {
+    response.map(|body| {
+        PermitBody {
+            inner: body,
+            _permit: permit,
+        }
+        .boxed_unsync()
+    })
+}
+
+/// An authorizer that gives each request it authorizes a permit, see
+/// [`ConcurrencyLimit::on_authorization`]
+///
+/// Authorizations made outside of a request to a [`ConcurrencyLimit`] are passed straight
+/// through.
+#[derive(Debug)]
+struct LimitedAuthorizer {
+    inner: Arc<dyn Authorizer>,
+    permits: Permits,
+}
+
+#[async_trait]
+impl Authorizer for LimitedAuthorizer {
+    async fn authorize(
+        &self,
+        token: Option<Vec<u8>>,
+        perms: &[Permission],
+    ) -> Result<Authorization, AuthzError> {
+        let authorization = self.inner.authorize(token, perms).await?;
+        // a request that is authorized more than once only takes a single permit
+        let admitted = ADMISSION.try_with(|admission| {
+            admission
+                .get_or_init(|| self.permits.try_acquire())
+                .is_some()
+        });
+        match admitted {
+            // the error is not seen by the client, the response is replaced by the limit
+            Ok(false) => Err(AuthzError::InvalidToken),
+            Ok(true) | Err(_) => Ok(authorization),
+        }
+    }
+
+    async fn probe(&self) -> Result<(), AuthzError> {
+        self.inner.probe().await
+    }
+}
+
+/// A response body that holds on to a concurrency limit permit until it is dropped
+struct PermitBody {
+    inner: BoxBody,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Body for PermitBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::num::NonZeroUsize;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use authz::{Authorization, Authorizer, Error as AuthzError, Permission};
+    use http::{Request, Response};
+    use tonic::body::BoxBody;
+    use tonic::{Code, Status};
+    use tower::{Service, ServiceExt};
+
+    use super::{ConcurrencyLimit, Permits};
+
+    fn grpc_status(response: &Response<BoxBody>) -> Option<Code> {
+        response
+            .headers()
+            .get("grpc-status")
+            .map(|status| Code::from_bytes(status.as_bytes()))
+    }
+
+    async fn call<S>(service: &mut S, req: Request<()>) -> Response<BoxBody>
+    where
+        S: Service<Request<()>, Response = Response<BoxBody>, Error = Infallible>,
+    {
+        service.ready().await.unwrap().call(req).await.unwrap()
+    }
+
+    /// Accepts the token `valid`, and no other
+    #[derive(Debug)]
+    struct TestAuthorizer;
+
+    #[async_trait]
+    impl Authorizer for TestAuthorizer {
+        async fn authorize(
+            &self,
+            token: Option<Vec<u8>>,
+            _perms: &[Permission],
+        ) -> Result<Authorization, AuthzError> {
+            match token.as_deref() {
+                Some(b"valid") => Ok(Authorization::new(None, vec![])),
+                Some(_) => Err(AuthzError::InvalidToken),
+                None => Err(AuthzError::NoToken),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let inner = tower::service_fn(|_: Request<()>| async {
+            Ok::<_, Infallible>(Response::new(tonic::body::empty_body()))
+        });
+        let permits = Permits::new(NonZeroUsize::new(1).unwrap());
+        let mut service = ConcurrencyLimit::new(inner, Some(permits));
+
+        // the permit is held for as long as the first response body is
+        let first = call(&mut service, Request::new(())).await;
+        assert_eq!(None, grpc_status(&first));
+        let second = call(&mut service, Request::new(())).await;
+        assert_eq!(Some(Code::ResourceExhausted), grpc_status(&second));
+
+        drop(first);
+        let third = call(&mut service, Request::new(())).await;
+        assert_eq!(None, grpc_status(&third));
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_requests_do_not_take_permits() {
+        let permits = Permits::new(NonZeroUsize::new(1).unwrap());
+        // authorizes requests as the Flight service does, with the authorizer it is given
+        let authz = permits.authorizer(Arc::new(TestAuthorizer));
+        let inner = tower::service_fn(move |req: Request<()>| {
+            let authz = Arc::clone(&authz);
+            async move {
+                let token = req
+                    .headers()
+                    .get("authorization")
+                    .map(|token| token.as_bytes().to_vec());
+                Ok::<_, Infallible>(match authz.authorize(token, &[]).await {
+                    Ok(_) => Response::new(tonic::body::empty_body()),
+                    Err(e) => Status::unauthenticated(e.to_string()).into_http(),
+                })
+            }
+        });
+        let mut service = ConcurrencyLimit::on_authorization(inner, Some(permits));
+        let req = |token: Option<&str>| {
+            let mut builder = Request::builder();
+            if let Some(token) = token {
+                builder = builder.header("authorization", token);
+            }
+            builder.body(()).unwrap()
+        };
+
+        // requests with an invalid or missing token are rejected, but do not hold a permit
+        let invalid = call(&mut service, req(Some("invalid"))).await;
+        assert_eq!(Some(Code::Unauthenticated), grpc_status(&invalid));
+        let missing = call(&mut service, req(None)).await;
+        assert_eq!(Some(Code::Unauthenticated), grpc_status(&missing));
+
+        let first = call(&mut service, req(Some("valid"))).await;
+        assert_eq!(None, grpc_status(&first));
+        let second = call(&mut service, req(Some("valid"))).await;
+        assert_eq!(Some(Code::ResourceExhausted), grpc_status(&second));
+        // once the limit is reached, unauthorized requests are still rejected for their token
+        let invalid = call(&mut service, req(Some("invalid"))).await;
+        assert_eq!(Some(Code::Unauthenticated), grpc_status(&invalid));
+
+        drop(first);
+        let third = call(&mut service, req(Some("valid"))).await;
+        assert_eq!(None, grpc_status(&third));
+    }
+}
@@ -13,10 +13,14 @@ clippy::future_not_send
 
 pub mod all_paths;
 mod grpc;
+pub use grpc::{
+    AuthzCacheConfig, FlightAuditor, FlightMethod, FlightRequestDescriptor, FlightRequestInfo,
+    GrpcCompressionConfig, GrpcConfig,
+};
 pub mod http;
 mod unified_service;
 
-use crate::grpc::make_flight_server;
+use crate::grpc::make_grpc_service;
 use crate::http::HttpApi;
 use crate::http::RecoveryHttpApi;
 use authz::Authorizer;
@@ -136,6 +140,9 @@ pub struct CreateServerArgs<'a> {
     pub cert_file: Option<PathBuf>,
     pub key_file: Option<PathBuf>,
     pub tls_minimum_version: &'a [&'static SupportedProtocolVersion],
+    pub grpc_config: GrpcConfig,
+    /// Passed information about every Flight request, e.g., for audit logging
+    pub flight_auditor: Option<Arc<dyn FlightAuditor>>,
 }
 
 #[derive(Debug)]
@@ -147,6 +154,8 @@ pub struct Server<'a> {
     key_file: Option<PathBuf>,
     cert_file: Option<PathBuf>,
     tls_minimum_version: &'a [&'static SupportedProtocolVersion],
+    grpc_config: GrpcConfig,
+    flight_auditor: Option<Arc<dyn FlightAuditor>>,
 }
 
 impl<'a> Server<'a> {
@@ -159,6 +168,8 @@ impl<'a> Server<'a> {
             cert_file,
             key_file,
             tls_minimum_version,
+            grpc_config,
+            flight_auditor,
         }: CreateServerArgs<'a>,
     ) -> Self {
         Self {
@@ -169,6 +180,8 @@ impl<'a> Server<'a> {
             key_file,
             cert_file,
             tls_minimum_version,
+            grpc_config,
+            flight_auditor,
         }
     }
 
@@ -372,10 +385,13 @@ pub async fn serve(
     let grpc_trace_layer = server.create_grpc_trace_layer();
 
     // Create gRPC service with trace layer
-    let grpc_service = make_flight_server(
+    let (grpc_service, mut grpc_health) = make_grpc_service(
         Arc::clone(&server.http.query_executor),
-        Some(server.authorizer()),
-    );
+        Some(Arc::clone(&server.authorizer)),
+        server.grpc_config.clone(),
+        server.flight_auditor.clone(),
+    )
+    .await;
 
     let key_file = server.key_file.clone();
     let cert_file = server.cert_file.clone();
@@ -408,6 +424,7 @@ pub async fn serve(
         );
 
         write_address_to_file(tcp_listener_file_path, &addr).await?;
+        grpc_health.set_serving(true).await;
 
         // Configure TLS
         let mut tls_config = ServerConfig::builder_with_protocol_versions(tls_min)
@@ -481,6 +498,7 @@ pub async fn serve(
         );
 
         write_address_to_file(tcp_listener_file_path, &addr).await?;
+        grpc_health.set_serving(true).await;
 
         // Connection handling loop
         loop {
@@ -526,6 +544,9 @@ pub async fn serve(
         }
     }
 
+    // stop advertising the gRPC services as available while connections are drained
+    grpc_health.set_serving(false).await;
+
     // This explicit select! is needed for graceful shutdown
     trace!("Starting graceful shutdown, waiting for connections to close");
     tokio::select! {
@@ -2038,6 +2059,8 @@ mod tests {
             cert_file: None,
             key_file: None,
             tls_minimum_version: TLS_MIN_VERSION,
+            grpc_config: Default::default(),
+            flight_auditor: None,
         });
         let shutdown = frontend_shutdown.clone();
         let paths = EMPTY_PATHS.get_or_init(std::vec::Vec::new);
@@ -358,6 +358,14 @@ impl WriteBufferImpl {
     ) -> Result<Vec<Arc<dyn QueryChunk>>, DataFusionError> {
         let span_ctx = ctx.span_ctx().map(|span| span.child("table_chunks"));
         let mut recorder = SpanRecorder::new(span_ctx);
+        recorder.set_metadata(
+            "db_name",
+            MetaValue::String(db_schema.name.to_string().into()),
+        );
+        recorder.set_metadata(
+            "table_name",
+            MetaValue::String(table_def.table_name.to_string().into()),
+        );
 
         let mut chunks = self.buffer.get_table_chunks(
             Arc::clone(&db_schema),
@@ -441,6 +449,7 @@ impl WriteBufferImpl {
 
             chunks.push(Arc::new(parquet_chunk));
         }
+        recorder.set_metadata("chunk_count", MetaValue::Int(chunks.len() as i64));
 
         Ok(chunks)
     }